@@ -0,0 +1,28 @@
+// Follow Santa's instructions, returning the floor he ends up on and the
+// (1-indexed) position of the first instruction that takes him below ground
+// level, if any.
+fn follow(input: &str) -> (i64, Option<usize>) {
+    let mut floor = 0;
+    let mut pos = None;
+    for (index, c) in input.chars().enumerate() {
+        if '(' == c {
+            floor += 1;
+        } else if ')' == c {
+            floor -= 1;
+            if floor < 0 && pos.is_none() {
+                pos = Some(index + 1);
+            }
+        }
+    }
+    (floor, pos)
+}
+
+pub fn final_floor(input: &str) -> i64 {
+    follow(input).0
+}
+
+/// The position of the instruction that first takes Santa below ground level,
+/// or `None` if he never goes below it.
+pub fn first_basement_position(input: &str) -> Option<usize> {
+    follow(input).1
+}
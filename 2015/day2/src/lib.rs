@@ -0,0 +1,46 @@
+use std::num::ParseIntError;
+
+// Line is in the form "<int>x<int>x<int>". Returns the dimensions smallest
+// first.
+fn dimensions(line: &str) -> Result<Vec<usize>, ParseIntError> {
+    let mut hlw = line
+        .trim()
+        .split('x')
+        .map(|elem| elem.parse())
+        .collect::<Result<Vec<usize>, _>>()?;
+    hlw.sort_unstable();
+    Ok(hlw)
+}
+
+/// The total area of wrapping paper required for every gift.
+pub fn wrapping_paper(input: &str) -> Result<usize, ParseIntError> {
+    let mut area_wrapping_paper_total = 0;
+    for line in input.lines() {
+        let hlw = dimensions(line)?;
+        // Compute the surface area of each side of the gift.
+        let area_hl = hlw[0]*hlw[1];
+        let area_hw = hlw[0]*hlw[2];
+        let area_lw = hlw[1]*hlw[2];
+        // Allow some slack, which is the smallest of the above areas.
+        let slack = hlw[0]*hlw[1];
+        // Compute the amount of wrapping paper required.
+        let area_wrapping_paper = 2*area_hl + 2*area_hw + 2*area_lw
+            + slack;
+        // Add it to the total.
+        area_wrapping_paper_total += area_wrapping_paper;
+    }
+    Ok(area_wrapping_paper_total)
+}
+
+/// The total length of ribbon required for every gift.
+pub fn ribbon(input: &str) -> Result<usize, ParseIntError> {
+    let mut length_ribbon_total = 0;
+    for line in input.lines() {
+        let hlw = dimensions(line)?;
+        // Wrap the smallest perimeter, then tie a bow as long as the volume.
+        let length_ribbon = 2*hlw[0] + 2*hlw[1]
+            + hlw[0]*hlw[1]*hlw[2];
+        length_ribbon_total += length_ribbon;
+    }
+    Ok(length_ribbon_total)
+}
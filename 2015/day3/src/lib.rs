@@ -0,0 +1,16 @@
+use aoc_common::{Direction, Point2, SparseGrid};
+
+/// Count the houses that get at least one present when `movers` Santas take
+/// turns following the directions, starting at the same house.
+pub fn houses_visited(input: &str, movers: usize) -> usize {
+    let mut positions = vec![Point2::ORIGIN; movers];
+    let mut presents: SparseGrid<usize> = SparseGrid::new();
+    presents.insert(Point2::ORIGIN, movers);
+    let directions = input.chars().filter_map(Direction::from_arrow);
+    for (turn, direction) in directions.enumerate() {
+        let mover = &mut positions[turn%movers];
+        *mover = mover.step(direction);
+        *presents.entry(*mover).or_insert(0) += 1;
+    }
+    presents.len()
+}
@@ -0,0 +1,17 @@
+/// Find the lowest positive number that, appended to `key`, gives an MD5
+/// digest starting with `zeros` zeros in hexadecimal.
+pub fn mine(key: &str, zeros: usize) -> u64 {
+    let key = key.trim();
+    for i in 1.. {
+        let test = format!("{}{}", key, i);
+        let digest = format!("{:x}", md5::compute(&test));
+        let check = digest
+            .chars()
+            .take(zeros)
+            .all(|elem| '0' == elem);
+        if check {
+            return i;
+        }
+    }
+    unreachable!()
+}
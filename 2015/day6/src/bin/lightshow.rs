@@ -0,0 +1,83 @@
+use std::{
+    env,
+    error::Error,
+    fs::{self, File},
+    io::BufWriter,
+    path::{Path, PathBuf},
+    process,
+};
+
+use aoc_2015_day6::{LightGrid, Lights, Rules, instructions};
+
+const USAGE: &str =
+    "Usage: lightshow [--brightness] [--frames <dir>] <instructions> <image>";
+
+// Write the lights as an image at `path`.
+fn write_image(grid: &LightGrid, white: u32, path: &Path)
+        -> Result<(), Box<dyn Error>> {
+    let file = File::create(path)
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    grid.write_image(white, BufWriter::new(file))?;
+    Ok(())
+}
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("lightshow: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), Box<dyn Error>> {
+    let mut rules = Rules::Boolean;
+    let mut frames = None;
+    let mut paths = Vec::new();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--brightness" => rules = Rules::Brightness,
+            "--frames" => frames = Some(PathBuf::from(args.next()
+                .ok_or(USAGE)?)),
+            _ if arg.starts_with("--") => return Err(USAGE.into()),
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+    let (input, image) = match paths.as_slice() {
+        [input, image] => (input, image),
+        _ => return Err(USAGE.into()),
+    };
+    let text = fs::read_to_string(input)
+        .map_err(|e| format!("{}: {}", input.display(), e))?;
+    let instructions = instructions(&text)
+        .map_err(|e| e.with_source(input.display().to_string()))?;
+    let mut grid = LightGrid::fitting(rules, &instructions);
+    // Every frame draws the brightest the lights ever get as white, which
+    // means running the show once beforehand to find out. Without frames,
+    // the brightest light at the end is white.
+    let white = match (&frames, rules) {
+        (Some(_), Rules::Brightness) => {
+            let mut rehearsal = grid.clone();
+            Some(instructions.iter().fold(0, |white, instruction| {
+                rehearsal.apply(instruction);
+                white.max(rehearsal.brightest())
+            }))
+        },
+        _ => None,
+    };
+    if let Some(dir) = &frames {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("{}: {}", dir.display(), e))?;
+    }
+    let digits = instructions.len().to_string().len();
+    for (index, instruction) in instructions.iter().enumerate() {
+        grid.apply(instruction);
+        if let Some(dir) = &frames {
+            let name = format!("frame-{:0width$}.{}", index + 1,
+                grid.image_extension(), width = digits);
+            write_image(&grid, white.unwrap_or(1), &dir.join(name))?;
+        }
+    }
+    let white = white.unwrap_or_else(|| grid.brightest());
+    write_image(&grid, white, image)?;
+    Ok(())
+}
@@ -0,0 +1,184 @@
+//! Lights grouped into blocks that every instruction treats alike.
+//!
+//! Each instruction's rectangle starts and ends at one of only a few `x`
+//! and `y` coordinates. Between two neighbouring coordinates, every column
+//! of lights is switched by exactly the same instructions, and so is every
+//! row. So instead of a cell for each light, `CompressedGrid` keeps a cell
+//! for each block of lights between neighbouring coordinates. With `n`
+//! instructions that's at most `2n` by `2n` cells, however far apart the
+//! coordinates are.
+
+use aoc_common::{DenseGrid, Point2};
+
+use crate::{Lights, Rules, SantaInstruction};
+
+/// Lights that follow instructions a block at a time.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CompressedGrid {
+    rules: Rules,
+    /// Where each block of columns starts, in order, and then where the
+    /// last one ends.
+    xs: Vec<usize>,
+    /// Where each block of rows starts, in order, and then where the last
+    /// one ends.
+    ys: Vec<usize>,
+    /// The lights of the block from `(xs[x], ys[y])` up to but not including
+    /// `(xs[x + 1], ys[y + 1])`, at `(x, y)`.
+    blocks: DenseGrid<u32>,
+}
+
+// The coordinates where rectangles start, and where they end plus 1, in
+// order and without repeats.
+fn edges<F>(instructions: &[SantaInstruction], mut range: F) -> Vec<usize>
+        where F: FnMut(&SantaInstruction) -> (usize, usize) {
+    let mut edges: Vec<usize> = instructions
+        .iter()
+        .flat_map(|instruction| {
+            let (start, end) = range(instruction);
+            vec![start, end + 1]
+        })
+        .collect();
+    edges.sort_unstable();
+    edges.dedup();
+    edges
+}
+
+// The index of `coordinate` in `edges`. Panics if it's not an edge.
+fn edge(edges: &[usize], coordinate: usize) -> usize {
+    edges.binary_search(&coordinate).unwrap_or_else(|_| {
+        panic!("{} isn't the edge of any instruction the grid fits",
+            coordinate)
+    })
+}
+
+impl CompressedGrid {
+    pub fn rules(&self) -> Rules {
+        self.rules
+    }
+
+    // The number of lights in the block at `block`.
+    fn area(&self, block: Point2<usize>) -> u64 {
+        let width = self.xs[block.x + 1] - self.xs[block.x];
+        let height = self.ys[block.y + 1] - self.ys[block.y];
+        width as u64*height as u64
+    }
+}
+
+impl Lights for CompressedGrid {
+    /// Lights split into blocks along the edges of every instruction's
+    /// rectangle.
+    fn fitting(rules: Rules, instructions: &[SantaInstruction]) -> Self {
+        let xs = edges(instructions, |i| (i.region.p1.x, i.region.p2.x));
+        let ys = edges(instructions, |i| (i.region.p1.y, i.region.p2.y));
+        let blocks = DenseGrid::new(xs.len().saturating_sub(1),
+            ys.len().saturating_sub(1), 0);
+        Self { rules, xs, ys, blocks }
+    }
+
+    /// Panics if the instruction's rectangle doesn't start and end on the
+    /// edges of blocks, which it does if it was one of the instructions the
+    /// grid was made to fit.
+    fn apply(&mut self, instruction: &SantaInstruction) {
+        let SantaInstruction { op, region } = *instruction;
+        let x1 = edge(&self.xs, region.p1.x);
+        let x2 = edge(&self.xs, region.p2.x + 1);
+        let y1 = edge(&self.ys, region.p1.y);
+        let y2 = edge(&self.ys, region.p2.y + 1);
+        for y in y1..y2 {
+            for block in &mut self.blocks.row_mut(y)[x1..x2] {
+                *block = self.rules.update(op, *block);
+            }
+        }
+    }
+
+    fn lit(&self) -> usize {
+        self.blocks
+            .iter()
+            .filter(|&(_, &block)| 0 < block)
+            .map(|(point, _)| self.area(point) as usize)
+            .sum()
+    }
+
+    fn total_brightness(&self) -> u64 {
+        self.blocks
+            .iter()
+            .map(|(point, &block)| u64::from(block)*self.area(point))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LightGrid, Operation, Rectangle, instructions, light_show};
+    use std::fs;
+
+    // Instructions for lights spread over a `size` by `size` grid, from a
+    // simple random number generator, so they're the same every time.
+    fn random_instructions(count: usize, size: u64, seed: u64) -> String {
+        let mut state = seed;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (state >> 33)%size
+        };
+        let mut input = String::new();
+        for _ in 0..count {
+            let op = ["turn on", "turn off", "toggle"][next() as usize%3];
+            input += &format!("{} {},{} through {},{}\n", op, next(), next(),
+                next(), next());
+        }
+        input
+    }
+
+    // Follow `input` on both kinds of grid, under both sets of rules, and
+    // check they agree.
+    fn assert_agrees(input: &str) {
+        for &rules in [Rules::Boolean, Rules::Brightness].iter() {
+            let dense: LightGrid = light_show(input, rules).unwrap();
+            let compressed: CompressedGrid = light_show(input, rules).unwrap();
+            assert_eq!(dense.lit(), compressed.lit());
+            assert_eq!(dense.total_brightness(),
+                compressed.total_brightness());
+        }
+    }
+
+    #[test]
+    fn agrees_with_dense_grid() {
+        assert_agrees(&fs::read_to_string("test0.txt").unwrap());
+        assert_agrees(&fs::read_to_string("input.txt").unwrap());
+        assert_agrees(&random_instructions(50, 1000, 6));
+        assert_agrees(&random_instructions(200, 40, 2015));
+        assert_agrees("");
+    }
+
+    #[test]
+    fn huge_grids() {
+        let input = "turn on 0,0 through 999999999,999999999\n\
+            toggle 1,1 through 999999998,999999998\n\
+            turn off 500000000,0 through 500000000,0\n";
+        let lights: CompressedGrid = light_show(input, Rules::Boolean)
+            .unwrap();
+        assert_eq!(3_999_999_995, lights.lit());
+        let lights: CompressedGrid = light_show(input, Rules::Brightness)
+            .unwrap();
+        assert_eq!(2_999_999_992_000_000_007, lights.total_brightness());
+        let input = random_instructions(300, 1_000_000_000, 1);
+        let lights: CompressedGrid = light_show(&input, Rules::Brightness)
+            .unwrap();
+        assert!(lights.blocks.width() < 600 && lights.blocks.height() < 600);
+        assert!(0 < lights.lit());
+    }
+
+    #[test]
+    #[should_panic(expected = "isn't the edge of any instruction")]
+    fn rejects_unfitted_instructions() {
+        let mut lights = CompressedGrid::fitting(Rules::Boolean,
+            &instructions("toggle 0,0 through 9,9").unwrap());
+        lights.apply(&SantaInstruction {
+            op: Operation::TurnOn,
+            region: Rectangle::new(Point2::new(2, 2), Point2::new(3, 3)),
+        });
+    }
+}
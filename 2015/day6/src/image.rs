@@ -0,0 +1,146 @@
+//! Pictures of the lights, as Netpbm images.
+//!
+//! Lights under the boolean rules are drawn as a PBM, black and white, with
+//! lit lights white. Lights under the brightness rules are drawn as a PGM,
+//! in shades of grey from black for a light that's off to white for the
+//! brightness given as white. Both are the binary forms of the formats
+//! (`P4` and `P5`), which most image viewers and converters can read. The
+//! top row of the image is the top row of the grid, as `Display` draws it.
+
+use std::io::{self, Write};
+
+use crate::{LightGrid, Rules};
+
+/// The highest grey a PGM can have.
+const PGM_MAX: u32 = 65_535;
+
+impl LightGrid {
+    /// The brightness of the brightest light, or 0 if they're all off.
+    pub fn brightest(&self) -> u32 {
+        self.lights().values().iter().copied().max().unwrap_or(0)
+    }
+
+    /// Write the lights as an image: a PBM under the boolean rules, or a PGM
+    /// under the brightness rules, with any light at least as bright as
+    /// `white` drawn white. Frames of the same show should share a `white`,
+    /// so that a light keeps its shade from one frame to the next.
+    pub fn write_image<W: Write>(&self, white: u32, out: W)
+            -> io::Result<()> {
+        match self.rules() {
+            Rules::Boolean => write_pbm(self, out),
+            Rules::Brightness => write_pgm(self, white, out),
+        }
+    }
+
+    /// The file extension for the kind of image `write_image` writes.
+    pub fn image_extension(&self) -> &'static str {
+        match self.rules() {
+            Rules::Boolean => "pbm",
+            Rules::Brightness => "pgm",
+        }
+    }
+}
+
+// A PBM has a bit for each pixel, 1 for black, with each row padded to a
+// whole byte.
+fn write_pbm<W: Write>(grid: &LightGrid, mut out: W) -> io::Result<()> {
+    let lights = grid.lights();
+    write!(out, "P4\n{} {}\n", lights.width(), lights.height())?;
+    let mut row_bytes = Vec::with_capacity(lights.width().div_ceil(8));
+    for row in lights.rows().rev() {
+        row_bytes.clear();
+        for eight in row.chunks(8) {
+            let byte = eight
+                .iter()
+                .enumerate()
+                .filter(|&(_, &light)| 0 == light)
+                .fold(0u8, |byte, (bit, _)| byte | 0x80 >> bit);
+            row_bytes.push(byte);
+        }
+        out.write_all(&row_bytes)?;
+    }
+    out.flush()
+}
+
+// A PGM has a byte for each pixel, or two, most significant first, when
+// the maximum grey is over 255. Brightnesses over `PGM_MAX` are scaled down
+// to fit.
+fn write_pgm<W: Write>(grid: &LightGrid, white: u32, mut out: W)
+        -> io::Result<()> {
+    let lights = grid.lights();
+    let white = white.max(1);
+    let max_grey = white.min(PGM_MAX);
+    write!(out, "P5\n{} {}\n{}\n", lights.width(), lights.height(),
+        max_grey)?;
+    let grey = |light: u32| {
+        let light = u64::from(light.min(white));
+        (light*u64::from(max_grey)/u64::from(white)) as u16
+    };
+    let mut row_bytes = Vec::new();
+    for row in lights.rows().rev() {
+        row_bytes.clear();
+        for &light in row {
+            let grey = grey(light);
+            if 255 < max_grey {
+                row_bytes.extend_from_slice(&grey.to_be_bytes());
+            } else {
+                row_bytes.push(grey as u8);
+            }
+        }
+        out.write_all(&row_bytes)?;
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Point2;
+    use crate::{Lights, Operation, Rectangle, SantaInstruction};
+
+    fn toggle(grid: &mut LightGrid, x1: usize, y1: usize, x2: usize,
+            y2: usize) {
+        grid.apply(&SantaInstruction {
+            op: Operation::Toggle,
+            region: Rectangle::new(Point2::new(x1, y1), Point2::new(x2, y2)),
+        });
+    }
+
+    fn image(grid: &LightGrid, white: u32) -> Vec<u8> {
+        let mut image = Vec::new();
+        grid.write_image(white, &mut image).unwrap();
+        image
+    }
+
+    #[test]
+    fn writes_pbms() {
+        let mut grid = LightGrid::new(Rules::Boolean, 10, 2);
+        toggle(&mut grid, 0, 0, 8, 0);
+        toggle(&mut grid, 1, 1, 1, 1);
+        assert_eq!("pbm", grid.image_extension());
+        let mut expected = b"P4\n10 2\n".to_vec();
+        expected.extend_from_slice(&[0b1011_1111, 0b1100_0000]);
+        expected.extend_from_slice(&[0b0000_0000, 0b0100_0000]);
+        assert_eq!(expected, image(&grid, 1));
+    }
+
+    #[test]
+    fn writes_pgms() {
+        let mut grid = LightGrid::new(Rules::Brightness, 3, 1);
+        toggle(&mut grid, 1, 0, 2, 0);
+        toggle(&mut grid, 2, 0, 2, 0);
+        assert_eq!(("pgm", 4), (grid.image_extension(), grid.brightest()));
+        let mut expected = b"P5\n3 1\n4\n".to_vec();
+        expected.extend_from_slice(&[0, 2, 4]);
+        assert_eq!(expected, image(&grid, grid.brightest()));
+        let mut expected = b"P5\n3 1\n2\n".to_vec();
+        expected.extend_from_slice(&[0, 2, 2]);
+        assert_eq!(expected, image(&grid, 2));
+        let mut expected = b"P5\n3 1\n300\n".to_vec();
+        expected.extend_from_slice(&[0, 0, 0, 2, 0, 4]);
+        assert_eq!(expected, image(&grid, 300));
+        let mut expected = b"P5\n3 1\n65535\n".to_vec();
+        expected.extend_from_slice(&[0, 0, 0, 1, 0, 2]);
+        assert_eq!(expected, image(&grid, 100_000));
+    }
+}
@@ -0,0 +1,324 @@
+pub mod compressed;
+pub mod image;
+
+use std::{
+    fmt::{Display, Formatter, self},
+    str::FromStr,
+};
+
+use aoc_common::{DenseGrid, ParseError, Point2, parse::{self, Cursor}};
+
+pub use compressed::CompressedGrid;
+
+/// The number of lights along each side of Santa's grid.
+pub const GRID_LENGTH: usize = 1000;
+
+/// The largest coordinate an instruction may use. Any larger, and the number
+/// of lights in a rectangle might not fit in a `usize`.
+pub const MAX_COORDINATE: usize = 999_999_999;
+
+/// What an instruction does to each light in its rectangle.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Operation {
+    Toggle,
+    TurnOff,
+    TurnOn,
+}
+
+/// How an `Operation` changes a light.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Rules {
+    /// Each light is on (1) or off (0).
+    Boolean,
+    /// Each light has a brightness: turning it on adds 1, turning it off
+    /// takes away 1 down to 0, and toggling it adds 2.
+    Brightness,
+}
+
+impl Rules {
+    /// The light after `op` is applied to it.
+    pub fn update(self, op: Operation, light: u32) -> u32 {
+        match (self, op) {
+            (Self::Boolean, Operation::Toggle) => (0 == light) as u32,
+            (Self::Boolean, Operation::TurnOff) => 0,
+            (Self::Boolean, Operation::TurnOn) => 1,
+            (Self::Brightness, Operation::Toggle) => light + 2,
+            (Self::Brightness, Operation::TurnOff) => light.saturating_sub(1),
+            (Self::Brightness, Operation::TurnOn) => light + 1,
+        }
+    }
+}
+
+/// The lights from `p1` to `p2`, including both corners.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Rectangle {
+    pub p1: Point2<usize>,
+    pub p2: Point2<usize>,
+}
+
+impl Rectangle {
+    /// The rectangle between two opposite corners, in either order.
+    pub fn new(a: Point2<usize>, b: Point2<usize>) -> Self {
+        Self {
+            p1: Point2::new(a.x.min(b.x), a.y.min(b.y)),
+            p2: Point2::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    /// The number of lights in the rectangle.
+    pub fn area(&self) -> usize {
+        (self.p2.x - self.p1.x + 1)*(self.p2.y - self.p1.y + 1)
+    }
+}
+
+// Parse a corner such as "499,500", which runs up to the next whitespace.
+fn corner(cursor: &mut Cursor<'_>) -> Result<Point2<usize>, ParseError> {
+    let column = cursor.column();
+    let text = cursor.take_while(|c| !c.is_whitespace());
+    let corner: Point2<usize> = text
+        .parse()
+        .map_err(|e: ParseError| e.offset(column - 1))?;
+    if MAX_COORDINATE < corner.x.max(corner.y) {
+        return Err(ParseError::new(column, format!(
+            "a corner with coordinates of at most {}", MAX_COORDINATE))
+            .found(text));
+    }
+    Ok(corner)
+}
+
+// Valid string (e.g.): "499,499 through 500,500".
+impl FromStr for Rectangle {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cursor = Cursor::new(s);
+        cursor.skip_whitespace();
+        let a = corner(&mut cursor)?;
+        cursor.skip_whitespace();
+        cursor.expect("through")?;
+        cursor.skip_whitespace();
+        let b = corner(&mut cursor)?;
+        cursor.end()?;
+        Ok(Self::new(a, b))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SantaInstruction {
+    pub op: Operation,
+    pub region: Rectangle,
+}
+
+// Valid string (e.g.): "turn on 0,0 through 999,999", "toggle 0,0 through
+// 999,0", or "turn off 499,499 through 500,500".
+impl FromStr for SantaInstruction {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cursor = Cursor::new(s);
+        cursor.skip_whitespace();
+        let op = if cursor.accept("toggle") {
+            Operation::Toggle
+        } else if cursor.accept("turn") {
+            cursor.skip_whitespace();
+            if cursor.accept("off") {
+                Operation::TurnOff
+            } else if cursor.accept("on") {
+                Operation::TurnOn
+            } else {
+                return Err(cursor.error("'on' or 'off'"));
+            }
+        } else {
+            return Err(cursor.error("'turn on', 'turn off', or 'toggle'"));
+        };
+        let column = cursor.column();
+        let region = cursor
+            .rest()
+            .parse()
+            .map_err(|e: ParseError| e.offset(column - 1))?;
+        Ok(Self { op, region })
+    }
+}
+
+/// Parse one instruction from each line of `input`.
+pub fn instructions(input: &str) -> Result<Vec<SantaInstruction>, ParseError> {
+    parse::lines(input)
+}
+
+/// A set of lights that instructions can be applied to.
+pub trait Lights {
+    /// All the lights off, ready to follow `instructions` under `rules`.
+    fn fitting(rules: Rules, instructions: &[SantaInstruction]) -> Self;
+
+    /// Apply an instruction to every light in its region.
+    fn apply(&mut self, instruction: &SantaInstruction);
+
+    /// The number of lights that are on at all.
+    fn lit(&self) -> usize;
+
+    /// The brightness of every light added together. Under the boolean
+    /// rules, this is the number lit.
+    fn total_brightness(&self) -> u64;
+}
+
+/// A rectangle of lights, all off to begin with, with `(0, 0)` in the
+/// bottom left corner.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LightGrid {
+    rules: Rules,
+    lights: DenseGrid<u32>,
+}
+
+impl LightGrid {
+    pub fn new(rules: Rules, width: usize, height: usize) -> Self {
+        Self { rules, lights: DenseGrid::new(width, height, 0) }
+    }
+
+    pub fn rules(&self) -> Rules {
+        self.rules
+    }
+
+    pub fn lights(&self) -> &DenseGrid<u32> {
+        &self.lights
+    }
+}
+
+impl Lights for LightGrid {
+    /// A grid big enough for every instruction, and at least `GRID_LENGTH`
+    /// lights along each side.
+    fn fitting(rules: Rules, instructions: &[SantaInstruction]) -> Self {
+        let (width, height) = instructions
+            .iter()
+            .fold((GRID_LENGTH, GRID_LENGTH), |(width, height), i| {
+                (width.max(i.region.p2.x + 1), height.max(i.region.p2.y + 1))
+            });
+        Self::new(rules, width, height)
+    }
+
+    /// Panics if the region doesn't fit on the grid.
+    fn apply(&mut self, instruction: &SantaInstruction) {
+        let SantaInstruction { op, region } = *instruction;
+        assert!(self.lights.contains(region.p2),
+            "{} is outside a {} by {} grid", region.p2, self.lights.width(),
+            self.lights.height());
+        for y in region.p1.y..=region.p2.y {
+            let row = &mut self.lights.row_mut(y)[region.p1.x..=region.p2.x];
+            for light in row {
+                *light = self.rules.update(op, *light);
+            }
+        }
+    }
+
+    fn lit(&self) -> usize {
+        self.lights.values().iter().filter(|&&light| 0 < light).count()
+    }
+
+    fn total_brightness(&self) -> u64 {
+        self.lights.values().iter().map(|&light| u64::from(light)).sum()
+    }
+}
+
+/// Each light's value separated by spaces, with a line for each row, top row
+/// first.
+impl Display for LightGrid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for row in self.lights.rows().rev() {
+            let row: Vec<String> = row.iter().map(u32::to_string).collect();
+            writeln!(f, "{}", row.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Follow every instruction in `input` under `rules`.
+pub fn light_show<L: Lights>(input: &str, rules: Rules)
+        -> Result<L, ParseError> {
+    let instructions = instructions(input)?;
+    let mut lights = L::fitting(rules, &instructions);
+    for instruction in &instructions {
+        lights.apply(instruction);
+    }
+    Ok(lights)
+}
+
+/// The number of lights left on, following the instructions as switches.
+pub fn lights_lit(input: &str) -> Result<usize, ParseError> {
+    Ok(light_show::<CompressedGrid>(input, Rules::Boolean)?.lit())
+}
+
+/// The total brightness of the lights, following the instructions as
+/// brightness controls.
+pub fn total_brightness(input: &str) -> Result<u64, ParseError> {
+    let lights = light_show::<CompressedGrid>(input, Rules::Brightness)?;
+    Ok(lights.total_brightness())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn day6_test0() {
+        let input = fs::read_to_string("test0.txt").unwrap();
+        assert_eq!(Ok(998_996), lights_lit(&input));
+        assert_eq!(Ok(1_001_996), total_brightness(&input));
+    }
+
+    #[test]
+    fn parses_instructions() {
+        let instruction = SantaInstruction {
+            op: Operation::TurnOff,
+            region: Rectangle::new(Point2::new(499, 499),
+                Point2::new(500, 500)),
+        };
+        assert_eq!(Ok(instruction),
+            "turn off 500,499 through 499,500".parse());
+        assert_eq!(4, instruction.region.area());
+        let error = |input| instructions(input).unwrap_err().to_string();
+        assert_eq!("2:6: expected 'on' or 'off', found 'u'",
+            error("toggle 0,0 through 1,1\nturn up 0,0 through 1,1"));
+        assert_eq!("1:1: expected 'turn on', 'turn off', or 'toggle', \
+            found 's'", error("switch 0,0 through 1,1"));
+        assert_eq!("1:12: expected 'through', found 't'",
+            error("toggle 0,0 thru 1,1"));
+        assert_eq!("1:23: expected a non-negative integer, found -1",
+            error("turn on 0,0 through 1,-1"));
+        assert_eq!("1:24: expected the end of the line, found 'a'",
+            error("toggle 0,0 through 1,1 and back"));
+        assert_eq!("1:21: expected a corner with coordinates of at most \
+            999999999, found 18446744073709551615,0",
+            error("turn on 0,0 through 18446744073709551615,0"));
+        assert_eq!("1:8: expected a corner with coordinates of at most \
+            999999999, found 0,1000000000",
+            error("toggle 0,1000000000 through 0,0"));
+        assert!(instructions("toggle 0,0 through 999999999,999999999")
+            .is_ok());
+    }
+
+    #[test]
+    fn rules() {
+        let mut grid = LightGrid::new(Rules::Boolean, 3, 2);
+        let all = Rectangle::new(Point2::new(0, 0), Point2::new(2, 1));
+        let corner = Rectangle::new(Point2::new(1, 1), Point2::new(2, 1));
+        grid.apply(&SantaInstruction { op: Operation::Toggle, region: all });
+        grid.apply(&SantaInstruction {
+            op: Operation::TurnOff,
+            region: corner,
+        });
+        assert_eq!("1 0 0\n1 1 1\n", grid.to_string());
+        assert_eq!((4, 4), (grid.lit(), grid.total_brightness()));
+        let mut grid = LightGrid::new(Rules::Brightness, 3, 2);
+        grid.apply(&SantaInstruction { op: Operation::Toggle, region: all });
+        grid.apply(&SantaInstruction {
+            op: Operation::TurnOn,
+            region: corner,
+        });
+        for _ in 0..4 {
+            grid.apply(&SantaInstruction {
+                op: Operation::TurnOff,
+                region: corner,
+            });
+        }
+        assert_eq!("2 0 0\n2 2 2\n", grid.to_string());
+        assert_eq!((4, 8), (grid.lit(), grid.total_brightness()));
+    }
+}
@@ -0,0 +1,37 @@
+use std::num::ParseIntError;
+
+/// The fuel required to launch a module of the given mass, ignoring the mass
+/// of the fuel itself.
+pub fn fuel_for_mass(mass: usize) -> usize {
+    (mass/3).saturating_sub(2)
+}
+
+/// The fuel required to launch a module of the given mass, including the
+/// fuel needed for the mass of its fuel.
+pub fn fuel_for_module(mass_module: usize) -> usize {
+    let mut fuel_module = fuel_for_mass(mass_module);
+    // Determine the fuel required for the mass of the fuel.
+    let mut fuel_mass = fuel_module;
+    loop {
+        fuel_mass /= 3;
+        if 2 < fuel_mass {
+            fuel_mass -= 2;
+            fuel_module += fuel_mass;
+        } else {
+            break;
+        }
+    }
+    fuel_module
+}
+
+/// Add up `fuel` for the mass of every module, one per line.
+pub fn total_fuel<F>(input: &str, fuel: F) -> Result<usize, ParseIntError>
+        where F: Fn(usize) -> usize {
+    let mut fuel_total = 0;
+    for line in input.lines() {
+        let mass_module: usize = line.trim().parse()?;
+        // Add the fuel requirement for this module to the total.
+        fuel_total += fuel(mass_module);
+    }
+    Ok(fuel_total)
+}
@@ -1,20 +1,21 @@
 use std::{
-    fs,
     cell::RefCell,
     cmp::Ordering,
 };
 
+use aoc_common::{DenseGrid, ParseError, Point2, gcd};
+
 #[derive(Clone, Debug, PartialEq)]
 struct Asteroid {
-    coordinates:  Point2D,
+    coordinates:  Point2<i32>,
     count_los:    usize,  // `los` -> line-of-sight
     polar_coords: PolarCoords,
     slopes:       Vec<Fraction>,
 }
 
 impl Asteroid {
-    fn new(coordinates: Point2D) -> Self {
-        let polar_coords = coordinates.to_polar();
+    fn new(coordinates: Point2<i32>) -> Self {
+        let polar_coords = to_polar(coordinates);
         Self {
             coordinates,
             count_los: 0,
@@ -41,38 +42,25 @@ impl Asteroid {
         self.count_los = self.slopes.len();
     }
 
-    fn set_origin(&mut self, origin: &Point2D) {
-        self.coordinates.x -= origin.x;
-        self.coordinates.y -= origin.y;
-        self.polar_coords = self.coordinates.to_polar();
+    fn set_origin(&mut self, origin: Point2<i32>) {
+        self.coordinates -= origin;
+        self.polar_coords = to_polar(self.coordinates);
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
-struct Point2D {
-    x: i32,
-    y: i32,
-}
-
-impl Point2D {
-    fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
-    }
-
-    fn to_polar(&self) -> PolarCoords {
-        let Point2D { x, y } = *self;
-        let r = f64::from(x*x + y*y).sqrt();
-        let temp = x;
-        let x = -y;
-        let y = temp;
-        let mut slope = Fraction::new(y, x);
-        slope.reduce();
-        let mut theta = f64::from(slope.num).atan2(f64::from(slope.den));
-        if theta < 0.0 {
-            theta += 2.0*std::f64::consts::PI;
-        }
-        PolarCoords { r, theta }
+fn to_polar(point: Point2<i32>) -> PolarCoords {
+    let Point2 { x, y } = point;
+    let r = f64::from(x*x + y*y).sqrt();
+    let temp = x;
+    let x = -y;
+    let y = temp;
+    let mut slope = Fraction::new(y, x);
+    slope.reduce();
+    let mut theta = f64::from(slope.num).atan2(f64::from(slope.den));
+    if theta < 0.0 {
+        theta += 2.0*std::f64::consts::PI;
     }
+    PolarCoords { r, theta }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -93,7 +81,7 @@ impl Fraction {
     }
     
     fn reduce(&mut self) {
-        let gcd = gcd(self.num, self.den).abs();
+        let gcd = gcd(self.num, self.den);
         if 0 != gcd {
             self.num /= gcd;
             self.den /= gcd;
@@ -101,22 +89,7 @@ impl Fraction {
     }
 }
 
-fn gcd(mut a: i32, mut b: i32) -> i32 {
-    while 0 != b {
-        let temp = b;
-        b = modulo(a, b);
-        a = temp;
-    }
-    a
-}
-
-fn modulo(a: i32, n: i32) -> i32 {
-    // Euclidean modulus:
-    // https://en.wikipedia.org/wiki/Modulo_operation#In_programming_languages
-    let subtraction = if a < 0 { 1 } else { 0 };
-    a - n.abs()*(a/n.abs() - subtraction)
-}
-
+// Panics if the field is empty, which `populate_field` never returns.
 fn get_best_asteroid(field: &[RefCell<Asteroid>]) -> Asteroid {
     let best_asteroid = field.iter().max_by(|x, y| {
         x.borrow().count_los.cmp(&y.borrow().count_los)
@@ -124,33 +97,45 @@ fn get_best_asteroid(field: &[RefCell<Asteroid>]) -> Asteroid {
     best_asteroid.borrow().clone()
 }
 
-fn populate_field(input: &str) -> Vec<RefCell<Asteroid>> {
-    let mut field = Vec::new();
-    for (y, line) in input.lines().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            if '#' == c {
-                let coords = Point2D::new(x as i32, y as i32);
-                let asteroid = Asteroid::new(coords);
-                field.push(RefCell::new(asteroid));
-            }
-        }
+fn populate_field(input: &str) -> Result<Vec<RefCell<Asteroid>>, ParseError> {
+    let map = DenseGrid::parse(input, |c| Some('#' == c))?;
+    let field: Vec<RefCell<Asteroid>> = map
+        .iter()
+        .filter(|&(_, &asteroid)| asteroid)
+        .map(|(point, _)| {
+            let coords = Point2::new(point.x as i32, point.y as i32);
+            RefCell::new(Asteroid::new(coords))
+        })
+        .collect();
+    if field.is_empty() {
+        return Err(ParseError::new(1, "an asteroid ('#')")
+            .found("the end of the input")
+            .at_line(map.height() + 1));
     }
     for asteroid in field.iter() {
         asteroid.borrow_mut().get_slopes(&field);
     }
-    field
+    Ok(field)
+}
+
+/// The best place for a monitoring station, and the number of asteroids it
+/// can detect.
+pub fn best_location(input: &str) -> Result<((i32, i32), usize), ParseError> {
+    let field = populate_field(input)?;
+    let asteroid = get_best_asteroid(&field);
+    let Point2 { x, y } = asteroid.coordinates;
+    Ok(((x, y), asteroid.count_los))
 }
 
-fn main() {
-    let input = fs::read_to_string("input/day10_part2_input.txt").expect("Error reading input file.");
-    let mut field = populate_field(&input);
+/// The coordinates of the 200th asteroid the laser at the best location
+/// vaporizes, as 100 times its x coordinate plus its y coordinate, or `None`
+/// if there aren't 200 asteroids for it to vaporize.
+pub fn vaporize(input: &str) -> Result<Option<i32>, ParseError> {
+    let mut field = populate_field(input)?;
     let asteroid = get_best_asteroid(&field);
     let origin = asteroid.coordinates;
-    let Point2D { x, y } = origin;
-    println!("Best asteroid's coordinates: {},{}.", x, y);
-    println!("Detectable asteroids from the best asteroid: {}", asteroid.count_los);
     for asteroid in field.iter() {
-        asteroid.borrow_mut().set_origin(&origin);
+        asteroid.borrow_mut().set_origin(origin);
     }
     for asteroid in field.iter() {
         asteroid.borrow_mut().get_slopes(&field);
@@ -171,52 +156,43 @@ fn main() {
     });
     let mut i = 0;
     while i < field.len() {
-        let Point2D { x, y } = field[i].borrow().coordinates;
+        let Point2 { x, y } = field[i].borrow().coordinates;
         if 0 == x && 0 == y {
             field.remove(i);
             break;
         }
         i += 1;
     }
+    if field.len() < 200 {
+        return Ok(None);
+    }
     let mut count_vaporized = 0;
     let mut last_vaporized_index = None;
-    while None == last_vaporized_index {
+    while last_vaporized_index.is_none() {
         let mut i = 0;
         while i < field.len() {
             let last_theta = field[i].borrow().polar_coords.theta;
             count_vaporized += 1;
-            println!("Vaporized {}: {:?}, {:?}",
-                count_vaporized,
-                field[i].borrow().coordinates,
-                field[i].borrow().polar_coords);
-            if 200 == count_vaporized { 
+            if 200 == count_vaporized {
                 last_vaporized_index = Some(i);
-                println!("{:?}", last_vaporized_index);
                 break;
             }
             field.remove(i);
             while i < field.len()
                     && last_theta == field[i].borrow().polar_coords.theta {
-                println!("Not vaporized {}: {:?}, {:?}",
-                count_vaporized,
-                field[i].borrow().coordinates,
-                field[i].borrow().polar_coords);
                 i = (i + 1)%field.len();
             }
         }
     }
-    let mut result = field[last_vaporized_index.unwrap()].borrow().coordinates.clone();
-    println!("{:?}", origin);
-    println!("{:?}", result);
-    result.x += origin.x;
-    result.y += origin.y;
-    println!("{:?}", result);
-    println!("Final answer: {}", 100*result.x + result.y);
+    let result = field[last_vaporized_index.unwrap()].borrow().coordinates
+        + origin;
+    Ok(Some(100*result.x + result.y))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
 
     #[test]
     fn day10_part1_tests() {
@@ -226,21 +202,38 @@ mod tests {
             "input/day10_part1_test2.txt",
             "input/day10_part1_test3.txt",
             "input/day10_part1_test4.txt",
-        ].into_iter();
+        ].iter();
         let expected_outputs = [
-            (Point2D { x:  3, y:  4 },   8),
-            (Point2D { x:  5, y:  8 },  33),
-            (Point2D { x:  1, y:  2 },  35),
-            (Point2D { x:  6, y:  3 },  41),
-            (Point2D { x: 11, y: 13 }, 210),
-        ].into_iter();
+            (Point2 { x:  3, y:  4 },   8),
+            (Point2 { x:  5, y:  8 },  33),
+            (Point2 { x:  1, y:  2 },  35),
+            (Point2 { x:  6, y:  3 },  41),
+            (Point2 { x: 11, y: 13 }, 210),
+        ].iter();
         for (filename, expected_output) in filenames.zip(expected_outputs) {
             let input = fs::read_to_string(filename)
                 .expect("Error reading input file.");
-            let field = populate_field(&input);
+            let field = populate_field(&input).unwrap();
             let asteroid = get_best_asteroid(&field);
             let actual_output = (asteroid.coordinates, asteroid.count_los);
             assert_eq!(*expected_output, actual_output);
         }
     }
+    #[test]
+    fn rejects_ragged_maps() {
+        let error = best_location(".#..#\n.....\n####\n").unwrap_err();
+        assert_eq!("3:5: expected a row 5 cells wide, found 4 cells",
+            error.to_string());
+    }
+
+    #[test]
+    fn needs_asteroids() {
+        for &input in ["", "...\n...\n"].iter() {
+            let error = best_location(input).unwrap_err();
+            assert_eq!(format!("{}:1: expected an asteroid ('#'), found the \
+                end of the input", input.lines().count() + 1),
+                error.to_string());
+        }
+        assert_eq!(Ok(None), vaporize("#.#\n.#.\n"));
+    }
 }
@@ -0,0 +1,276 @@
+use std::{
+    convert::TryInto,
+    fmt::{Display, Formatter, self},
+};
+
+use aoc_common::{ParseError, Point3, num::lcm_all, parse};
+
+#[derive(Debug)]
+struct System {
+    moons: Vec<Moon>,
+    step: usize,
+    te: u32,
+}
+
+impl Display for System {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for m in self.moons.iter() {
+            write!(f, "{}", m)?;
+        }
+        Ok(())
+    }
+}
+
+impl System {
+    fn new() -> Self {
+        Self { moons: Vec::new(), step: 0, te: 0 }
+    }
+
+    // A moon at each position listed in `input`, of which there must be at
+    // least one.
+    fn new_from_str(input: &str) -> Result<Self, ParseError> {
+        let positions = parse::lines::<Point3<i32>>(input)?;
+        if positions.is_empty() {
+            return Err(ParseError::new(1, "at least one moon")
+                .found("the end of the input")
+                .at_line(1));
+        }
+        let mut system = System::new();
+        for position in positions {
+            system.push(Moon::new(position));
+        }
+        Ok(system)
+    }
+
+    fn push(&mut self, m: Moon) {
+        self.moons.push(m);
+    }
+
+    fn simulate_quiet(&mut self, steps: usize) -> u32 {
+        for _ in 1..=steps {
+            self.update();
+            self.step += 1;
+        }
+        self.te
+    }
+
+
+    fn update(&mut self) {
+        for i in 0..self.moons.len() - 1 {
+            for j in i + 1..self.moons.len() {
+                self.update_velocities(i, j);
+            }
+        }
+        for moon in self.moons.iter_mut() {
+            moon.update_position();
+            moon.update_energies();
+        }
+        self.update_total_energy();
+    }
+
+    fn update_total_energy(&mut self) {
+        self.te = self.moons
+            .iter()
+            .fold(0, |acc, x| acc + x.te);
+    }
+
+    fn update_velocities(&mut self, i: usize, j: usize) {
+        // Update x-velocities.
+        if self.moons[i].pos.x < self.moons[j].pos.x {
+            self.moons[i].vel.x += 1;
+            self.moons[j].vel.x -= 1;
+        } else if self.moons[j].pos.x < self.moons[i].pos.x {
+            self.moons[j].vel.x += 1;
+            self.moons[i].vel.x -= 1;
+        }
+        // Update y-velocities.
+        if self.moons[i].pos.y < self.moons[j].pos.y {
+            self.moons[i].vel.y += 1;
+            self.moons[j].vel.y -= 1;
+        } else if self.moons[j].pos.y < self.moons[i].pos.y {
+            self.moons[j].vel.y += 1;
+            self.moons[i].vel.y -= 1;
+        }
+        // Update z-velocities.
+        if self.moons[i].pos.z < self.moons[j].pos.z {
+            self.moons[i].vel.z += 1;
+            self.moons[j].vel.z -= 1;
+        } else if self.moons[j].pos.z < self.moons[i].pos.z {
+            self.moons[j].vel.z += 1;
+            self.moons[i].vel.z -= 1;
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Moon {
+    ke: u32,          // kinetic energy
+    pe: u32,          // potential energy
+    pos: Point3<i32>, // position
+    te: u32,          // total energy
+    vel: Point3<i32>, // velocity
+}
+
+impl Display for Moon {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "pos={}, vel={}, pot={}, kin={}",
+            self.pos, self.vel, self.pe, self.ke)
+    }
+}
+
+impl Moon {
+    fn new(pos: Point3<i32>) -> Self {
+        let mut moon = Self {
+            pos,
+            vel: Point3::ORIGIN,
+            ke: 0,
+            pe: 0,
+            te: 0,
+        };
+        moon.update_energies();
+        moon.update_energies();
+        moon
+    }
+
+    fn update_position(&mut self) {
+        // Position is just the old position plus the velocity.
+        self.pos += self.vel;
+    }
+
+    fn update_energies(&mut self) {
+        self.ke = self.vel.manhattan(Point3::ORIGIN).try_into().unwrap();
+        self.pe = self.pos.manhattan(Point3::ORIGIN).try_into().unwrap();
+        self.te = self.ke*self.pe;
+    }
+}
+
+
+#[derive(Clone, Debug)]
+struct CycleCounter {
+    done: bool,
+    cycle: Vec<i32>,
+}
+
+impl CycleCounter {
+    fn new() -> Self {
+        Self { done: false, cycle: Vec::new() }
+    }
+}
+
+fn is_repeating(slice: &[i32]) -> bool {
+    if !slice.len().is_multiple_of(2) { return false; }
+    for i in 0..slice.len()/2 {
+        if slice[i] != slice[i + slice.len()/2] {
+            return false;
+        }
+    }
+    true
+}
+
+/// The total energy in the system after simulating `steps` time steps.
+pub fn total_energy(input: &str, steps: usize) -> Result<u32, ParseError> {
+    let mut system = System::new_from_str(input)?;
+    Ok(system.simulate_quiet(steps))
+}
+
+/// The number of time steps before the moons first return to a previous
+/// state.
+pub fn repeat_period(input: &str) -> Result<usize, ParseError> {
+    Ok(find_repeat_period(input)?.0)
+}
+
+/// The number of time steps before the moons first return to a previous
+/// state, and the number of time steps it took to find that out.
+pub fn find_repeat_period(input: &str)
+        -> Result<(usize, usize), ParseError> {
+    let mut system = System::new_from_str(input)?;
+    let mut cyclecounters: Vec<Vec<CycleCounter>>
+        = vec![vec![CycleCounter::new(); 3]; system.moons.len()];
+    let mut count_done = 0;
+    let mut steps = 0;
+    while count_done < cyclecounters.len()*cyclecounters[0].len() {
+        for (moon, counters) in system.moons.iter().zip(&mut cyclecounters) {
+            for (j, counter) in counters.iter_mut().enumerate() {
+                if !counter.done {
+                    if 0 == j {
+                        counter.cycle.push(moon.pos.x);
+                    } else if 1 == j {
+                        counter.cycle.push(moon.pos.y);
+                    } else if 2 == j {
+                        counter.cycle.push(moon.pos.z);
+                    }
+                    if is_repeating(&counter.cycle) {
+                        counter.done = true;
+                        let cycle_len = counter.cycle.len()/2;
+                        counter.cycle.resize(cycle_len, 0);
+                        count_done += 1;
+                    }
+                }
+            }
+        }
+        system.simulate_quiet(1);
+        steps += 1;
+    }
+    let mut v = Vec::new();
+    for counters in cyclecounters.iter() {
+        for counter in counters.iter() {
+            v.push(counter.cycle.len());
+        }
+    }
+    v.sort_unstable();
+    v.dedup();
+    Ok((lcm_all(v), steps))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn day12_part1_test0() {
+        let input = fs::read_to_string("input/day12-part1-test0.txt")
+            .expect("Error reading input file.");
+        let mut system = System::new_from_str(&input)
+            .expect("Error generating system.");
+        assert_eq!(179, system.simulate_quiet(10));
+    }
+
+    #[test]
+    fn day12_part1_test1() {
+        let input = fs::read_to_string("input/day12-part1-test1.txt")
+            .expect("Error reading input file.");
+        let mut system = System::new_from_str(&input)
+            .expect("Error generating system.");
+        assert_eq!(1940, system.simulate_quiet(100));
+    }
+
+    #[test]
+    fn day12_part1_test() {
+        let input = fs::read_to_string("input/day12-part1-input.txt")
+            .expect("Error reading input file.");
+        let mut system = System::new_from_str(&input)
+            .expect("Error generating system.");
+        assert_eq!(7758, system.simulate_quiet(1000));
+    }
+
+    #[test]
+    fn reports_malformed_moons() {
+        let input = "<x=-1, y=0, z=2>\n<x=2, y=-10, z=-7>\n<x=1, q=2, z=3>\n";
+        let error = total_energy(input, 10).unwrap_err();
+        assert_eq!("3:7: expected 'y=', found 'q'", error.to_string());
+    }
+
+    #[test]
+    fn skips_blank_lines_but_needs_a_moon() {
+        let input = "<x=-1, y=0, z=2>\n\n<x=2, y=-10, z=-7>\n";
+        assert_eq!(Ok(2), System::new_from_str(input).map(|s| s.moons.len()));
+        let error = "1:1: expected at least one moon, found the end of the \
+            input";
+        for &input in ["", "\n\n"].iter() {
+            let energy = total_energy(input, 10).unwrap_err();
+            assert_eq!(error, energy.to_string());
+            assert_eq!(error, repeat_period(input).unwrap_err().to_string());
+        }
+    }
+}
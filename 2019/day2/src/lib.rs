@@ -0,0 +1,64 @@
+use intcode::{
+    Intcode,
+    IntcodeError,
+    InstructionSet,
+    search::{SearchError, find_noun_and_verb},
+};
+
+pub const MAGIC_NUM: i64 = 19_690_720;
+
+// Day 2 predates every opcode but Add, Multiply, and Halt.
+fn day2_machine(program: &[i64]) -> Intcode {
+    let mut vm = Intcode::new(program.to_vec());
+    vm.set_instruction_set(InstructionSet::Day2);
+    vm
+}
+
+/// Run the program with `noun` and `verb` written to addresses 1 and 2,
+/// returning what it leaves in address 0.
+pub fn run_nounverb(program: &[i64], noun: i64, verb: i64)
+        -> Result<i64, IntcodeError> {
+    let mut vm = day2_machine(program);
+    vm.poke(1, noun)?;
+    vm.poke(2, verb)?;
+    vm.run_to_halt()?;
+    Ok(vm.memory()[0])
+}
+
+/// Find the first noun and verb that make the program leave `MAGIC_NUM` in
+/// address 0.
+pub fn find_nounverb(program: &[i64])
+        -> Result<Option<(i64, i64)>, SearchError> {
+    let vm = day2_machine(program);
+    Ok(find_noun_and_verb(&vm, MAGIC_NUM, 0..100)?.first().copied())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use intcode::parse_source;
+
+    #[test]
+    fn day2_answer() {
+        let program = parse_source(Path::new("input.txt")).unwrap();
+        assert_eq!(Ok(10566835), run_nounverb(&program, 12, 2));
+        assert_eq!(Ok(Some((23, 47))), find_nounverb(&program));
+    }
+
+    #[test]
+    fn rejects_later_opcodes() {
+        let mut vm = Intcode::new(vec![1101, 1, 1, 0, 99]);
+        vm.set_instruction_set(InstructionSet::Day2);
+        assert_eq!(Err(IntcodeError::InvalidParameterMode {
+            ip: 0,
+            opcode: 1101,
+            parameter: 1,
+            mode: 1,
+        }), vm.run());
+        let mut vm = Intcode::new(vec![3, 0, 99]);
+        vm.set_instruction_set(InstructionSet::Day2);
+        assert_eq!(Err(IntcodeError::UnknownOpcode { ip: 0, opcode: 3 }),
+            vm.run());
+    }
+}
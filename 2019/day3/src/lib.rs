@@ -0,0 +1,139 @@
+use std::str::FromStr;
+
+use aoc_common::{
+    Direction,
+    ParseError,
+    Point2,
+    SparseGrid,
+    parse::{self, Cursor},
+};
+
+#[derive(Debug)]
+struct DirLenInstruction {
+    dir: Direction,
+    len: usize,
+}
+
+// Valid string: "Rx", "Ux", "Lx", or "Dx", where x is an integer.
+impl FromStr for DirLenInstruction {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cursor = Cursor::new(s);
+        let dir = cursor
+            .peek()
+            .and_then(Direction::from_letter)
+            .ok_or_else(|| cursor.error("'U', 'R', 'D', or 'L'"))?;
+        cursor.next_char();
+        let len = cursor.integer()?;
+        cursor.end()?;
+        Ok(Self { dir, len })
+    }
+}
+
+#[derive(Debug)]
+struct WireRouteDirLen(Vec<DirLenInstruction>);
+
+// Valid string (e.g.): "R75,D30,R83,U83,L12,D49,R71,U7,L72".
+impl FromStr for WireRouteDirLen {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(parse::separated(s, ',')?))
+    }
+}
+
+// Every point a wire passes through, mapped to the number of steps the wire
+// takes to first reach it.
+#[derive(Debug, Eq, PartialEq)]
+struct WireRoutePoint2D(SparseGrid<usize>);
+
+impl From<WireRouteDirLen> for WireRoutePoint2D {
+    fn from(wrdl: WireRouteDirLen) -> Self {
+        let mut current_pos = Point2::ORIGIN;
+        let mut route = SparseGrid::new();
+        let mut path_length: usize = 0;
+        for dli in wrdl.0.iter() {
+            for _ in 1..=dli.len {
+                current_pos = current_pos.step(dli.dir);
+                path_length += 1;
+                route.entry(current_pos).or_insert(path_length);
+            }
+        }
+        Self(route)
+    }
+}
+
+// TODO: clean up unnecessary types (e.g. DirLenInstruction, maybe
+// WireRouteDirLen).
+
+// Parse the wire on line `line` of `input`.
+fn wire(input: &str, line: usize) -> Result<WireRoutePoint2D, ParseError> {
+    let route = match input.lines().nth(line - 1) {
+        Some(route) => route.parse::<WireRouteDirLen>(),
+        None => Err(ParseError::new(1, "a wire")
+            .found("the end of the input")),
+    };
+    Ok(route.map_err(|e| e.at_line(line))?.into())
+}
+
+// Find the points the two wires in `input` share, mapped to the combined
+// number of steps both wires take to reach them.
+fn common_points(input: &str) -> Result<SparseGrid<usize>, ParseError> {
+    let wrp2d1 = wire(input, 1)?;
+    let wrp2d2 = wire(input, 2)?;
+    // Find which points are common to both sets.
+    Ok(wrp2d1.0
+        .iter()
+        .filter_map(|(point, steps)| {
+            let other_steps = wrp2d2.0.get(point)?;
+            Some((point, steps + other_steps))
+        })
+        .collect())
+}
+
+/// The Manhattan distance from the origin to the closest point where the
+/// wires cross, or `None` if they never cross.
+pub fn closest_crossing(input: &str) -> Result<Option<u32>, ParseError> {
+    Ok(common_points(input)?
+        .iter()
+        .map(|(point, _)| point.manhattan(Point2::ORIGIN) as u32)
+        .min())
+}
+
+/// The fewest combined steps the wires take to reach a point where they
+/// cross, or `None` if they never cross.
+pub fn fewest_steps(input: &str) -> Result<Option<usize>, ParseError> {
+    Ok(common_points(input)?.iter().map(|(_, &steps)| steps).min())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn day3_tests() {
+        let cases = [
+            ("test0.txt", 6, 30),
+            ("test1.txt", 159, 610),
+            ("test2.txt", 135, 410),
+        ];
+        for &(path, distance, steps) in cases.iter() {
+            let input = fs::read_to_string(path).unwrap();
+            assert_eq!(Ok(Some(distance)), closest_crossing(&input));
+            assert_eq!(Ok(Some(steps)), fewest_steps(&input));
+        }
+    }
+
+    #[test]
+    fn reports_malformed_wires() {
+        let error = |input| closest_crossing(input).unwrap_err().to_string();
+        assert_eq!("2:4: expected 'U', 'R', 'D', or 'L', found 'X'",
+            error("R8,U5\nU7,X6"));
+        assert_eq!("1:9: expected the end of the line, found 'U'",
+            error("R8,U5,L5U3\nU7"));
+        assert_eq!("1:2: expected an integer, found the end of the line",
+            error("R\nU7"));
+        assert_eq!("2:1: expected a wire, found the end of the input",
+            error("R8,U5\n"));
+    }
+}
@@ -0,0 +1,87 @@
+use std::num::ParseIntError;
+
+// Count how many times each digit appears in a password, or return `None` if
+// its digits ever decrease from left to right. Since the digits never
+// decrease, equal digits are always adjacent.
+fn digit_counts(mut password: usize) -> Option<[u8; 10]> {
+    let mut last = password%10;
+    let mut counts = [0u8; 10];
+    counts[last] += 1;
+    password /= 10;
+    for _ in 0..(password as f64).log10() as usize + 1 {
+        let lsd = password%10;
+        counts[lsd] += 1;
+        if last < lsd {
+            return None;
+        } else {
+            last = lsd;
+        }
+        password /= 10;
+    }
+    Some(counts)
+}
+
+// Part 1: the digits never decrease, and two adjacent digits are the same.
+fn satisfies_loose_criteria_num(password: usize) -> bool {
+    digit_counts(password).is_some_and(|counts| {
+        counts.iter().any(|&count| 2 <= count)
+    })
+}
+
+// Part 2: as part 1, but some pair of adjacent matching digits isn't part of
+// a larger group of matching digits.
+fn satisfies_criteria_num(password: usize) -> bool {
+    digit_counts(password).is_some_and(|counts| counts.contains(&2))
+}
+
+// Input is in the form "<lower>-<upper>".
+fn count_passwords<F>(input: &str, criteria: F)
+        -> Result<usize, ParseIntError>
+        where F: Fn(usize) -> bool {
+    let mut bounds = input.trim().splitn(2, '-');
+    let lbound: usize = bounds.next().unwrap_or("").parse()?;
+    let ubound: usize = bounds.next().unwrap_or("").parse()?;
+    Ok((lbound..=ubound).filter(|&i| criteria(i)).count())
+}
+
+/// Count the passwords in the range that meet the part 1 criteria.
+pub fn count_loose(input: &str) -> Result<usize, ParseIntError> {
+    count_passwords(input, satisfies_loose_criteria_num)
+}
+
+/// Count the passwords in the range that meet the part 2 criteria.
+pub fn count_strict(input: &str) -> Result<usize, ParseIntError> {
+    count_passwords(input, satisfies_criteria_num)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test1()  { assert!(!satisfies_criteria_num(111111)); }
+    #[test]
+    fn test2()  { assert!(!satisfies_criteria_num(223450)); }
+    #[test]
+    fn test3()  { assert!(!satisfies_criteria_num(123789)); }
+    #[test]
+    fn test4()  { assert!( satisfies_criteria_num(112233)); }
+    #[test]
+    fn test5()  { assert!(!satisfies_criteria_num(123444)); }
+    #[test]
+    fn test6()  { assert!( satisfies_criteria_num(111122)); }
+    #[test]
+    fn test7()  { assert!(!satisfies_criteria_num(111123)); }
+    #[test]
+    fn test8()  { assert!(!satisfies_criteria_num(135679)); }
+    #[test]
+    #[allow(clippy::zero_prefixed_literal)]
+    fn test9()  { assert!(!satisfies_criteria_num(012210)); }
+    #[test]
+    fn test10() { assert!(!satisfies_criteria_num(135659)); }
+    #[test]
+    fn test11() { assert!( satisfies_loose_criteria_num(111111)); }
+    #[test]
+    fn test12() { assert!(!satisfies_loose_criteria_num(223450)); }
+    #[test]
+    fn test13() { assert!(!satisfies_loose_criteria_num(123789)); }
+}
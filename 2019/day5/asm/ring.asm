@@ -0,0 +1,27 @@
+; A token ring for a network of four machines. Machine 0 starts a packet
+; with y = 1 on its way to machine 1; every machine that receives a packet
+; passes it on to the next address with y incremented. The last machine
+; sends it to the NAT at address 255 instead.
+        IN [addr]
+        JT [addr], #loop
+        OUT #1
+        OUT #0
+        OUT #1
+loop:   IN [x]
+        EQ [x], #-1, [t]
+        JT [t], #loop
+        IN [y]
+        ADD [addr], #1, [dest]
+        EQ [dest], #4, [t]
+        JF [t], #send
+        ADD #255, #0, [dest]
+send:   OUT [dest]
+        OUT [x]
+        ADD [y], #1, [y]
+        OUT [y]
+        JT #1, #loop
+addr:   DATA 0
+x:      DATA 0
+y:      DATA 0
+dest:   DATA 0
+t:      DATA 0
@@ -0,0 +1,9 @@
+; Read integers until a 0 is read, then output their sum.
+loop:   IN [n]
+        JF [n], #done
+        ADD [sum], [n], [sum]
+        JT #1, #loop
+done:   OUT [sum]
+        HLT
+n:      DATA 0
+sum:    DATA 0
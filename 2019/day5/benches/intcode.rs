@@ -0,0 +1,65 @@
+use std::{
+    fs,
+    path::Path,
+};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use intcode::{Engine, Intcode, parse_source, search::find_noun_and_verb};
+
+const ENGINES: [(&str, Engine); 2] = [
+    ("reference", Engine::Reference),
+    ("cached", Engine::Cached),
+];
+
+// Every day 2 and day 5 program, sorted by file name.
+fn programs() -> Vec<(String, Vec<i64>)> {
+    let mut programs: Vec<(String, Vec<i64>)> = fs::read_dir("input")
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            let name = path.file_name().unwrap().to_string_lossy();
+            name.starts_with("day2-") || name.starts_with("day5-")
+        })
+        .map(|path| {
+            let name = path.file_stem().unwrap().to_string_lossy();
+            (name.into_owned(), parse_source(&path).unwrap())
+        })
+        .collect();
+    programs.sort();
+    programs
+}
+
+// Run each program once, with 5 as input for the programs that read one.
+fn programs_bench(c: &mut Criterion) {
+    for (name, program) in programs() {
+        let mut group = c.benchmark_group(name);
+        for &(engine_name, engine) in ENGINES.iter() {
+            let mut vm = Intcode::new(program.clone());
+            vm.set_engine(engine);
+            group.bench_function(engine_name, |b| b.iter(|| {
+                vm.reset(&program);
+                vm.push_input(5);
+                vm.run_until_blocked().unwrap()
+            }));
+        }
+        group.finish();
+    }
+}
+
+// Day 2's brute-force search, which runs the same program 10,000 times.
+fn search_bench(c: &mut Criterion) {
+    let program = parse_source(Path::new("input/day2-input2.txt")).unwrap();
+    let mut group = c.benchmark_group("day2-search");
+    group.sample_size(10);
+    for &(engine_name, engine) in ENGINES.iter() {
+        let mut vm = Intcode::new(program.clone());
+        vm.set_engine(engine);
+        group.bench_function(engine_name, |b| b.iter(|| {
+            find_noun_and_verb(&vm, 19_690_720, 0..100).unwrap()
+        }));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, programs_bench, search_bench);
+criterion_main!(benches);
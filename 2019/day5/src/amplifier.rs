@@ -0,0 +1,114 @@
+use crate::{
+    error::IntcodeError,
+    vm::{Intcode, RunState},
+};
+
+/// How a bank of amplifiers is connected.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Wiring {
+    /// Each amplifier's first output feeds the next, once.
+    Series,
+    /// The last amplifier's output loops back into the first until the
+    /// amplifiers halt.
+    Feedback,
+}
+
+/// Run one amplifier per phase setting, each a copy of `program`. Every
+/// amplifier reads its phase setting first; the first amplifier then reads
+/// 0. Returns the last signal sent to the thrusters, which is `None` if the
+/// last amplifier never produced one.
+pub fn run_amplifiers(program: &[i64], phases: &[i64], wiring: Wiring)
+        -> Result<Option<i64>, IntcodeError> {
+    let mut amplifiers: Vec<Intcode> = phases
+        .iter()
+        .map(|&phase| {
+            let mut vm = Intcode::new(program.to_vec());
+            vm.push_input(phase);
+            vm
+        })
+        .collect();
+    let mut signal = Some(0);
+    let mut thrust = None;
+    loop {
+        for (i, vm) in amplifiers.iter_mut().enumerate() {
+            if let Some(value) = signal.take() {
+                vm.push_input(value);
+            }
+            match vm.run()? {
+                RunState::Output(value) => signal = Some(value),
+                RunState::Halted => return Ok(thrust),
+                RunState::NeedsInput => return Err(vm.input_exhausted()),
+                RunState::BudgetExhausted => {
+                    return Err(vm.budget_exhausted());
+                },
+            }
+            if i + 1 == phases.len() {
+                thrust = signal;
+            }
+        }
+        if Wiring::Series == wiring {
+            return Ok(thrust);
+        }
+    }
+}
+
+/// Try every ordering of `phase_set`, returning the ordering that sends the
+/// highest signal to the thrusters along with that signal.
+pub fn max_thruster_signal(program: &[i64], phase_set: &[i64], wiring: Wiring)
+        -> Result<Option<(Vec<i64>, i64)>, IntcodeError> {
+    let mut best: Option<(Vec<i64>, i64)> = None;
+    for phases in permutations(phase_set) {
+        if let Some(signal) = run_amplifiers(program, &phases, wiring)? {
+            if best.as_ref().is_none_or(|&(_, max)| max < signal) {
+                best = Some((phases, signal));
+            }
+        }
+    }
+    Ok(best)
+}
+
+/// Every ordering of `items`, generated with Heap's algorithm.
+pub fn permutations(items: &[i64]) -> Vec<Vec<i64>> {
+    let mut items = items.to_vec();
+    let mut counters = vec![0; items.len()];
+    let mut result = vec![items.clone()];
+    let mut i = 1;
+    while i < items.len() {
+        if counters[i] < i {
+            let j = if 0 == i%2 { 0 } else { counters[i] };
+            items.swap(j, i);
+            result.push(items.clone());
+            counters[i] += 1;
+            i = 1;
+        } else {
+            counters[i] = 0;
+            i += 1;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permutations_are_distinct_and_complete() {
+        let mut perms = permutations(&[0, 1, 2, 3]);
+        assert_eq!(24, perms.len());
+        perms.sort();
+        perms.dedup();
+        assert_eq!(24, perms.len());
+        assert_eq!(vec![Vec::<i64>::new()], permutations(&[]));
+    }
+
+    #[test]
+    fn feedback_loop_example() {
+        let program = [
+            3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4,
+            27, 1001, 28, -1, 28, 1005, 28, 6, 99, 0, 0, 5,
+        ];
+        assert_eq!(Ok(Some(139629729)),
+            run_amplifiers(&program, &[9, 8, 7, 6, 5], Wiring::Feedback));
+    }
+}
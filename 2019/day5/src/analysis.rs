@@ -0,0 +1,238 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{Display, Formatter, self},
+    ops::Range,
+};
+
+use crate::instruction::{Instruction, Operand};
+
+/// What static analysis found out about a program image without running it.
+///
+/// Code is discovered by following control flow from address 0. A jump with
+/// an immediate target adds an edge to that target; a jump whose target is
+/// read from memory can go anywhere, so it is flagged as dynamic and its
+/// targets are left unexplored. A jump whose condition is immediate only
+/// takes the branch the condition selects.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Analysis {
+    /// Every reachable instruction, by address.
+    pub instructions: BTreeMap<usize, Instruction>,
+    /// The addresses control can move to after each reachable instruction.
+    pub successors: BTreeMap<usize, Vec<usize>>,
+    /// Reachable jumps whose targets come from memory.
+    pub dynamic_jumps: BTreeSet<usize>,
+    /// Reachable addresses that don't begin a well-formed instruction.
+    pub invalid: BTreeSet<usize>,
+    /// Every cell written through a positional destination, mapped to the
+    /// instructions that write it.
+    pub writes: BTreeMap<usize, Vec<usize>>,
+    /// Reachable instructions writing through a relative destination, whose
+    /// target depends on the relative base at run time.
+    pub dynamic_writes: BTreeSet<usize>,
+    len: usize,
+}
+
+impl Analysis {
+    /// Written cells that are also words control reaches: the program's
+    /// self-modifying spots.
+    pub fn self_modifying(&self) -> Vec<usize> {
+        let code = self.reached_words();
+        self.writes
+            .keys()
+            .copied()
+            .filter(|address| code.contains(address))
+            .collect()
+    }
+
+    /// Runs of words that control never reaches. These are probably data.
+    pub fn unreachable(&self) -> Vec<Range<usize>> {
+        let code = self.reached_words();
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        for address in (0..self.len).filter(|a| !code.contains(a)) {
+            match ranges.last_mut() {
+                Some(range) if range.end == address => range.end += 1,
+                _ => ranges.push(address..address + 1),
+            }
+        }
+        ranges
+    }
+
+    // The words of every reachable instruction, plus the reachable addresses
+    // that didn't decode.
+    fn reached_words(&self) -> BTreeSet<usize> {
+        self.instructions
+            .iter()
+            .flat_map(|(&address, instruction)| {
+                address..address + instruction.size()
+            })
+            .chain(self.invalid.iter().copied())
+            .collect()
+    }
+}
+
+impl Display for Analysis {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let self_modifying: BTreeSet<usize> =
+            self.self_modifying().into_iter().collect();
+        writeln!(f, "Code:")?;
+        for (&address, instruction) in &self.instructions {
+            let modified = (address..address + instruction.size())
+                .any(|a| self_modifying.contains(&a));
+            let marker = if modified { '*' } else { ' ' };
+            let mut line = format!("{}{:>5}: {:<32}", marker, address,
+                instruction.to_string());
+            let successors = self.successors[&address]
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>();
+            if !successors.is_empty() {
+                line += &format!(" -> {}", successors.join(", "));
+            }
+            if self.dynamic_jumps.contains(&address) {
+                line += " (dynamic jump)";
+            }
+            if self.dynamic_writes.contains(&address) {
+                line += " (dynamic write)";
+            }
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        for address in &self.invalid {
+            write!(f, "!{:>5}: invalid instruction", address)?;
+            if self_modifying.contains(address) {
+                write!(f, " (overwritten at run time)")?;
+            }
+            writeln!(f)?;
+        }
+        writeln!(f, "Self-modified cells: {}", join(&self_modifying))?;
+        let data = self.unreachable()
+            .iter()
+            .map(|range| format!("{}..{}", range.start, range.end))
+            .collect::<Vec<_>>();
+        writeln!(f, "Unreachable (probable data): {}", data.join(", "))
+    }
+}
+
+fn join(addresses: &BTreeSet<usize>) -> String {
+    addresses
+        .iter()
+        .map(|address| address.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Explore a program image from address 0 without running it.
+pub fn analyze(program: &[i64]) -> Analysis {
+    let mut analysis = Analysis { len: program.len(), ..Analysis::default() };
+    let mut pending = vec![0];
+    while let Some(address) = pending.pop() {
+        if analysis.instructions.contains_key(&address)
+                || analysis.invalid.contains(&address) {
+            continue;
+        }
+        let instruction = match Instruction::decode(program, address) {
+            Ok(i) if address + i.size() <= program.len() => i,
+            _ => {
+                analysis.invalid.insert(address);
+                continue;
+            },
+        };
+        match instruction.destination() {
+            Some(Operand::Address(cell)) if 0 <= cell => {
+                analysis.writes
+                    .entry(cell as usize)
+                    .or_default()
+                    .push(address);
+            },
+            Some(Operand::Relative(_)) => {
+                analysis.dynamic_writes.insert(address);
+            },
+            // An immediate or negative destination always fails.
+            Some(_) => {
+                analysis.invalid.insert(address);
+                continue;
+            },
+            None => (),
+        }
+        let successors = successors(&instruction, address);
+        if let Instruction::JumpIfTrue { address: target, .. }
+                | Instruction::JumpIfFalse { address: target, .. }
+                = instruction {
+            if !matches!(target, Operand::Immediate(_)) {
+                analysis.dynamic_jumps.insert(address);
+            }
+        }
+        pending.extend(successors.iter().rev());
+        analysis.successors.insert(address, successors);
+        analysis.instructions.insert(address, instruction);
+    }
+    for writers in analysis.writes.values_mut() {
+        writers.sort_unstable();
+    }
+    analysis
+}
+
+/// Analyze a program image into a printable report.
+pub fn report(program: &[i64]) -> String {
+    analyze(program).to_string()
+}
+
+// The statically known addresses control can move to after `instruction`.
+fn successors(instruction: &Instruction, address: usize) -> Vec<usize> {
+    let next = address + instruction.size();
+    let (condition, target, jump_if) = match *instruction {
+        Instruction::Halt => return Vec::new(),
+        Instruction::JumpIfTrue { condition, address } => {
+            (condition, address, true)
+        },
+        Instruction::JumpIfFalse { condition, address } => {
+            (condition, address, false)
+        },
+        _ => return vec![next],
+    };
+    let (taken, not_taken) = match condition {
+        Operand::Immediate(value) => (jump_if == (0 != value),
+            jump_if != (0 != value)),
+        _ => (true, true),
+    };
+    let mut successors = Vec::new();
+    if not_taken {
+        successors.push(next);
+    }
+    if let (true, Operand::Immediate(target)) = (taken, target) {
+        if 0 <= target && !successors.contains(&(target as usize)) {
+            successors.push(target as usize);
+        }
+    }
+    successors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn follows_jumps_and_finds_data() {
+        // Read n; if it's 0 jump to the Halt, otherwise rewrite the Halt and
+        // jump to 13, which would run off the end of the program.
+        let program = [
+            3, 14, 1006, 14, 12, 1101, 0, 99, 12, 1106, 0, 13, 99, 7, 0, 0,
+        ];
+        let analysis = analyze(&program);
+        let addresses: Vec<usize> =
+            analysis.instructions.keys().copied().collect();
+        assert_eq!(vec![0, 2, 5, 9, 12], addresses);
+        assert_eq!(vec![5, 12], analysis.successors[&2]);
+        // JF #0 always jumps, and 13 isn't a valid instruction.
+        assert_eq!(vec![13], analysis.successors[&9]);
+        assert!(analysis.invalid.contains(&13));
+        assert_eq!(vec![12], analysis.self_modifying());
+        assert_eq!(vec![14..16], analysis.unreachable());
+        assert!(analysis.dynamic_jumps.is_empty());
+
+        let analysis = analyze(&[1105, 1, 4, 0, 5, 5, 7, 99, 0]);
+        assert_eq!(vec![4], analysis.successors[&0]);
+        assert_eq!(vec![7], analysis.successors[&4]);
+        assert!(analysis.dynamic_jumps.contains(&4));
+        assert_eq!(vec![3..4, 8..9], analysis.unreachable());
+    }
+}
@@ -0,0 +1,433 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{Display, Formatter, self},
+};
+
+use crate::instruction::{Instruction, Operand};
+
+/// Everything that can go wrong while assembling. Line numbers are 1-indexed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AsmError {
+    UnknownMnemonic { line: usize, mnemonic: String },
+    OperandCount {
+        line: usize,
+        mnemonic: String,
+        expected: usize,
+        found: usize,
+    },
+    InvalidOperand { line: usize, operand: String },
+    /// An instruction writes through an immediate operand.
+    ImmediateDestination { line: usize },
+    InvalidLabel { line: usize, label: String },
+    DuplicateLabel { line: usize, label: String },
+    UndefinedLabel { line: usize, label: String },
+    /// A label's address plus its offset doesn't fit in a word.
+    Overflow { line: usize, label: String },
+}
+
+impl Display for AsmError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownMnemonic { line, mnemonic } => write!(f,
+                "line {}: unknown mnemonic {:?}", line, mnemonic),
+            Self::OperandCount { line, mnemonic, expected, found } => write!(f,
+                "line {}: {} takes {} operand(s), found {}",
+                line, mnemonic, expected, found),
+            Self::InvalidOperand { line, operand } => write!(f,
+                "line {}: invalid operand {:?}", line, operand),
+            Self::ImmediateDestination { line } => write!(f,
+                "line {}: cannot write through an immediate operand", line),
+            Self::InvalidLabel { line, label } => write!(f,
+                "line {}: invalid label {:?}", line, label),
+            Self::DuplicateLabel { line, label } => write!(f,
+                "line {}: label {:?} is already defined", line, label),
+            Self::UndefinedLabel { line, label } => write!(f,
+                "line {}: label {:?} is not defined", line, label),
+            Self::Overflow { line, label } => write!(f,
+                "line {}: label {:?} plus its offset is too large for a word",
+                line, label),
+        }
+    }
+}
+
+impl Error for AsmError {}
+
+// A number, or a label's address (times a sign of 1 or -1) plus an offset,
+// resolved once every label is known.
+#[derive(Debug)]
+enum Value {
+    Number(i64),
+    Label { sign: i64, label: String, offset: i64 },
+}
+
+impl Value {
+    // `None` if the negated number or offset doesn't fit in a word.
+    fn negate(self) -> Option<Self> {
+        match self {
+            Self::Number(n) => Some(Self::Number(n.checked_neg()?)),
+            Self::Label { sign, label, offset } => Some(Self::Label {
+                sign: -sign,
+                label,
+                offset: offset.checked_neg()?,
+            }),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Mode {
+    Address,
+    Immediate,
+    Relative,
+}
+
+#[derive(Debug)]
+enum Statement {
+    Instruction {
+        line: usize,
+        mnemonic: String,
+        operands: Vec<(Mode, Value)>,
+    },
+    Data {
+        line: usize,
+        values: Vec<Value>,
+    },
+}
+
+/// Assemble mnemonic source into an Intcode program image.
+///
+/// Each line holds any number of `label:` definitions, followed by an
+/// optional instruction or `DATA` directive, followed by an optional
+/// `; comment`. Instructions use the disassembler's mnemonics (ADD, MUL, IN,
+/// OUT, JT, JF, LT, EQ, ARB, HLT) and operand syntax: `[a]` is positional,
+/// `#v` is immediate, and `[rb+o]` or `[rb-o]` is relative. `DATA` emits its
+/// comma-separated operands verbatim. Anywhere a number is expected, a label
+/// (optionally followed by `+n` or `-n`) may be used instead; it stands for
+/// the address of the word following the label definition. In `[rb-o]`, the
+/// whole of `o` is subtracted, so `[rb-top+1]` is `top + 1` below the base.
+///
+/// ```text
+/// loop:   IN [n]
+///         JF [n], #done
+///         OUT [n]
+///         JT #1, #loop
+/// done:   HLT
+/// n:      DATA 0
+/// ```
+pub fn assemble(src: &str) -> Result<Vec<i64>, AsmError> {
+    let mut labels = HashMap::new();
+    let mut statements = Vec::new();
+    let mut address = 0;
+    for (index, text) in src.lines().enumerate() {
+        let line = index + 1;
+        let mut text = match text.find(';') {
+            Some(i) => &text[..i],
+            None => text,
+        }.trim();
+        while let Some(i) = text.find(':') {
+            let label = text[..i].trim();
+            if !is_identifier(label) {
+                return Err(AsmError::InvalidLabel {
+                    line,
+                    label: label.to_string(),
+                });
+            }
+            if labels.insert(label.to_string(), address).is_some() {
+                return Err(AsmError::DuplicateLabel {
+                    line,
+                    label: label.to_string(),
+                });
+            }
+            text = text[i + 1..].trim();
+        }
+        if text.is_empty() {
+            continue;
+        }
+        let statement = parse_statement(line, text)?;
+        address += match &statement {
+            Statement::Instruction { operands, .. } => operands.len() + 1,
+            Statement::Data { values, .. } => values.len(),
+        };
+        statements.push(statement);
+    }
+    let mut program = Vec::with_capacity(address);
+    for statement in statements {
+        match statement {
+            Statement::Instruction { line, mnemonic, operands } => {
+                let mut resolved = Vec::with_capacity(operands.len());
+                for (mode, value) in operands {
+                    let value = resolve(line, value, &labels)?;
+                    resolved.push(match mode {
+                        Mode::Address => Operand::Address(value),
+                        Mode::Immediate => Operand::Immediate(value),
+                        Mode::Relative => Operand::Relative(value),
+                    });
+                }
+                let instruction = build(&mnemonic, &resolved);
+                let destination = instruction.destination();
+                if let Some(Operand::Immediate(_)) = destination {
+                    return Err(AsmError::ImmediateDestination { line });
+                }
+                program.extend(instruction.encode());
+            },
+            Statement::Data { line, values } => {
+                for value in values {
+                    program.push(resolve(line, value, &labels)?);
+                }
+            },
+        }
+    }
+    Ok(program)
+}
+
+fn parse_statement(line: usize, text: &str) -> Result<Statement, AsmError> {
+    let (mnemonic, rest) = match text.find(char::is_whitespace) {
+        Some(i) => (&text[..i], text[i..].trim()),
+        None => (text, ""),
+    };
+    let mnemonic = mnemonic.to_uppercase();
+    let fields: Vec<&str> = if rest.is_empty() {
+        Vec::new()
+    } else {
+        rest.split(',').map(str::trim).collect()
+    };
+    if "DATA" == mnemonic {
+        let values = fields
+            .iter()
+            .map(|field| parse_value(field).ok_or_else(|| {
+                AsmError::InvalidOperand { line, operand: field.to_string() }
+            }))
+            .collect::<Result<_, _>>()?;
+        return Ok(Statement::Data { line, values });
+    }
+    let expected = operand_count(&mnemonic)
+        .ok_or_else(|| AsmError::UnknownMnemonic {
+            line,
+            mnemonic: mnemonic.clone(),
+        })?;
+    if expected != fields.len() {
+        return Err(AsmError::OperandCount {
+            line,
+            mnemonic,
+            expected,
+            found: fields.len(),
+        });
+    }
+    let operands = fields
+        .iter()
+        .map(|field| parse_operand(field).ok_or_else(|| {
+            AsmError::InvalidOperand { line, operand: field.to_string() }
+        }))
+        .collect::<Result<_, _>>()?;
+    Ok(Statement::Instruction { line, mnemonic, operands })
+}
+
+fn parse_operand(s: &str) -> Option<(Mode, Value)> {
+    if let Some(value) = s.strip_prefix('#') {
+        return Some((Mode::Immediate, parse_value(value.trim())?));
+    }
+    let inner = s.strip_prefix('[')?.strip_suffix(']')?.trim();
+    match inner.strip_prefix("rb") {
+        Some("") => Some((Mode::Relative, Value::Number(0))),
+        Some(offset) if offset.starts_with('+') => {
+            Some((Mode::Relative, parse_value(offset[1..].trim())?))
+        },
+        Some(offset) if offset.starts_with('-') => {
+            Some((Mode::Relative, parse_value(offset[1..].trim())?.negate()?))
+        },
+        _ => Some((Mode::Address, parse_value(inner)?)),
+    }
+}
+
+fn parse_value(s: &str) -> Option<Value> {
+    if let Ok(n) = s.parse() {
+        return Some(Value::Number(n));
+    }
+    let (label, offset) = match s.find(['+', '-']) {
+        Some(i) => {
+            let offset: i64 = s[i + 1..].trim().parse().ok()?;
+            let negative = s[i..].starts_with('-');
+            let offset = if negative { offset.checked_neg()? } else { offset };
+            (s[..i].trim(), offset)
+        },
+        None => (s, 0),
+    };
+    if is_identifier(label) {
+        Some(Value::Label { sign: 1, label: label.to_string(), offset })
+    } else {
+        None
+    }
+}
+
+fn resolve(line: usize, value: Value, labels: &HashMap<String, usize>)
+        -> Result<i64, AsmError> {
+    match value {
+        Value::Number(n) => Ok(n),
+        Value::Label { sign, label, offset } => match labels.get(&label) {
+            Some(&address) => match (sign*address as i64).checked_add(offset) {
+                Some(value) => Ok(value),
+                None => Err(AsmError::Overflow { line, label }),
+            },
+            None => Err(AsmError::UndefinedLabel { line, label }),
+        },
+    }
+}
+
+// Labels must not shadow the relative base register.
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    let starts_well = match chars.next() {
+        Some(c) => c.is_ascii_alphabetic() || '_' == c,
+        None => false,
+    };
+    starts_well
+        && chars.all(|c| c.is_ascii_alphanumeric() || '_' == c)
+        && "rb" != s
+}
+
+fn operand_count(mnemonic: &str) -> Option<usize> {
+    match mnemonic {
+        "HLT" => Some(0),
+        "IN" | "OUT" | "ARB" => Some(1),
+        "JT" | "JF" => Some(2),
+        "ADD" | "MUL" | "LT" | "EQ" => Some(3),
+        _ => None,
+    }
+}
+
+// Build an instruction whose mnemonic and operand count were already checked.
+fn build(mnemonic: &str, ops: &[Operand]) -> Instruction {
+    match mnemonic {
+        "ADD" => Instruction::Add {
+            op1: ops[0],
+            op2: ops[1],
+            address: ops[2],
+        },
+        "ARB" => Instruction::AdjustRelativeBase(ops[0]),
+        "EQ" => Instruction::TestEqual {
+            op1: ops[0],
+            op2: ops[1],
+            address: ops[2],
+        },
+        "HLT" => Instruction::Halt,
+        "IN" => Instruction::Input(ops[0]),
+        "JF" => Instruction::JumpIfFalse {
+            condition: ops[0],
+            address: ops[1],
+        },
+        "JT" => Instruction::JumpIfTrue {
+            condition: ops[0],
+            address: ops[1],
+        },
+        "LT" => Instruction::TestLessThan {
+            op1: ops[0],
+            op2: ops[1],
+            address: ops[2],
+        },
+        "MUL" => Instruction::Multiply {
+            op1: ops[0],
+            op2: ops[1],
+            address: ops[2],
+        },
+        "OUT" => Instruction::Output(ops[0]),
+        _ => unreachable!("unchecked mnemonic {}", mnemonic),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::disasm::{self, Item};
+
+    // Print a program as the disassembler decodes it, one mnemonic or DATA
+    // line per item, and assemble that text again.
+    fn reassemble(program: &[i64]) -> Result<Vec<i64>, AsmError> {
+        let src: Vec<String> = disasm::disassemble(program)
+            .iter()
+            .map(|line| match line.item {
+                Item::Code(instruction) => instruction.to_string(),
+                Item::Data(value) => format!("DATA {}", value),
+            })
+            .collect();
+        assemble(&src.join("\n"))
+    }
+
+    #[test]
+    fn assembles_day5_examples() {
+        assert_eq!(Ok(vec![1002, 4, 3, 4, 99]),
+            assemble("MUL [4], #3, [4]\nHLT"));
+        assert_eq!(Ok(vec![1101, 100, -1, 4, 99]),
+            assemble("ADD #100, #-1, [end] ; Overwrites HLT's operand.\n\
+                      end: HLT"));
+        assert_eq!(Ok(vec![109, 1, 204, -1, 99]),
+            assemble("arb #1\nout [rb-1]\nhlt"));
+    }
+
+    #[test]
+    fn round_trips_through_disassembler() {
+        let src = "\
+            start: IN [n]\n\
+                   JF [n], #done\n\
+                   OUT [n]\n\
+                   JT #1, #start\n\
+            done:  HLT\n\
+            n:     DATA 0, n+1";
+        let program = assemble(src).unwrap();
+        assert_eq!(vec![3, 11, 1006, 11, 10, 4, 11, 1105, 1, 0, 99, 0, 12],
+            program);
+        assert_eq!(Ok(program.clone()), reassemble(&program));
+        // ARB #-2, OUT [rb+1], ADD #3, #4, [rb-1], HLT, then data that
+        // doesn't decode.
+        let program = vec![109, -2, 204, 1, 21101, 3, 4, -1, 99, 11101, 7];
+        assert_eq!(Ok(program.clone()), reassemble(&program));
+    }
+
+    #[test]
+    fn relative_operands_take_labels_either_way() {
+        assert_eq!(Ok(vec![204, 6, 204, -6, 204, -8, 99]),
+            assemble("OUT [rb+top]\nOUT [rb-top]\n\
+                      OUT [rb-top+2]\ntop: HLT"));
+    }
+
+    #[test]
+    fn reports_errors_by_line() {
+        assert_eq!(Err(AsmError::UnknownMnemonic {
+            line: 2,
+            mnemonic: "NOP".to_string(),
+        }), assemble("HLT\nnop"));
+        assert_eq!(Err(AsmError::UndefinedLabel {
+            line: 1,
+            label: "nowhere".to_string(),
+        }), assemble("JT #1, #nowhere"));
+        assert_eq!(Err(AsmError::ImmediateDestination { line: 1 }),
+            assemble("ADD #1, #2, #3"));
+        assert_eq!(Err(AsmError::OperandCount {
+            line: 1,
+            mnemonic: "OUT".to_string(),
+            expected: 1,
+            found: 2,
+        }), assemble("OUT #1, #2"));
+    }
+
+    #[test]
+    fn reports_values_too_large_for_a_word() {
+        let operands = [
+            "[rb--9223372036854775808]",
+            "#top--9223372036854775808",
+            "[rb-top+-9223372036854775808]",
+        ];
+        for operand in &operands {
+            assert_eq!(Err(AsmError::InvalidOperand {
+                line: 2,
+                operand: operand.to_string(),
+            }), assemble(&format!("top: HLT\nOUT {}", operand)));
+        }
+        assert_eq!(Err(AsmError::Overflow {
+            line: 2,
+            label: "top".to_string(),
+        }), assemble("HLT\ntop: DATA top+9223372036854775807"));
+        assert_eq!(Ok(vec![204, i64::MIN + 1]),
+            assemble("OUT [rb-9223372036854775807]"));
+    }
+}
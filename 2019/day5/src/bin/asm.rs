@@ -0,0 +1,17 @@
+use std::{
+    env,
+    error::Error,
+    fs,
+};
+
+use intcode::{asm, format_source};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let path = env::args()
+        .nth(1)
+        .ok_or("Usage: asm <source.asm>")?;
+    let src = fs::read_to_string(&path)?;
+    let program = asm::assemble(&src)?;
+    println!("{}", format_source(&program));
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use std::{
+    env,
+    error::Error,
+    path::Path,
+};
+
+use intcode::checkpoint::Checkpoint;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (old, new) = match args.as_slice() {
+        [old, new] => (old, new),
+        _ => return Err("Usage: ckdiff <old.ck> <new.ck>".into()),
+    };
+    let old = Checkpoint::load(Path::new(old))?;
+    let new = Checkpoint::load(Path::new(new))?;
+    let differences = old.diff(&new);
+    if differences.is_empty() {
+        println!("The checkpoints are identical.");
+    }
+    for difference in differences {
+        println!("{}", difference);
+    }
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use std::{
+    env,
+    error::Error,
+    io::{self, BufRead, Write},
+    path::Path,
+};
+
+use intcode::{Intcode, debugger::Debugger, parse_source};
+
+// The number of executed instructions that can be rewound.
+const HISTORY_LIMIT: usize = 10_000;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let path = env::args()
+        .nth(1)
+        .ok_or("Usage: debug <program.txt>")?;
+    let src = parse_source(Path::new(&path))?;
+    let mut debugger = Debugger::new(Intcode::new(src), HISTORY_LIMIT);
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    writeln!(out, "Type `help` for a list of commands.")?;
+    loop {
+        write!(out, "(icdb) ")?;
+        out.flush()?;
+        let mut line = String::new();
+        if 0 == stdin.lock().read_line(&mut line)? {
+            break;
+        }
+        if !debugger.command(&line, &mut out)? {
+            break;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,16 @@
+use std::{
+    env,
+    error::Error,
+    path::Path,
+};
+
+use intcode::{disasm, parse_source};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let path = env::args()
+        .nth(1)
+        .ok_or("Usage: disasm <program.txt>")?;
+    let src = parse_source(Path::new(&path))?;
+    print!("{}", disasm::listing(&src));
+    Ok(())
+}
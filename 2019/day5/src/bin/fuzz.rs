@@ -0,0 +1,31 @@
+use std::{
+    env,
+    error::Error,
+};
+
+use intcode::fuzz::{self, Rng};
+
+const FUEL: u64 = 10_000;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args = env::args().skip(1);
+    let usage = "Usage: fuzz <iterations> [seed]";
+    let iterations: u64 = args.next().ok_or(usage)?.parse()?;
+    let seed = match args.next() {
+        Some(seed) => seed.parse()?,
+        None => 2019,
+    };
+    let mut rng = Rng::new(seed);
+    let mut halted = 0;
+    for _ in 0..iterations {
+        let program = fuzz::random_program(&mut rng);
+        let input = fuzz::random_input(&mut rng);
+        let outcome = fuzz::check(&program, &input, FUEL)
+            .map_err(|mismatch| mismatch.to_string())?;
+        if fuzz::Stop::Halted == outcome.stop {
+            halted += 1;
+        }
+    }
+    println!("{} programs agreed, {} of them halted", iterations, halted);
+    Ok(())
+}
@@ -0,0 +1,301 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, self},
+    fs,
+    io,
+    path::Path,
+    str::FromStr,
+};
+
+use crate::instruction::InstructionSet;
+
+/// Everything needed to resume an `Intcode` machine, as captured by
+/// `Intcode::checkpoint`.
+///
+/// Checkpoints are saved as text, one `key value` field per line, with lists
+/// of values comma-separated like a program image:
+///
+/// ```text
+/// intcode-checkpoint 1
+/// ip 0
+/// relative-base 0
+/// steps 0
+/// memory-limit 16777216
+/// instruction-set day9
+/// input 8
+/// output
+/// memory 3,9,8,9,10,9,4,9,99,-1,8
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    pub memory: Vec<i64>,
+    pub ip: usize,
+    pub relative_base: i64,
+    pub steps: u64,
+    pub memory_limit: usize,
+    pub instruction_set: InstructionSet,
+    /// Queued input values that haven't been read yet, oldest first.
+    pub input: Vec<i64>,
+    /// Output values collected by `run_until_blocked` that haven't been
+    /// taken yet, oldest first.
+    pub output: Vec<i64>,
+}
+
+const HEADER: &str = "intcode-checkpoint 1";
+
+/// Everything that can go wrong while loading a checkpoint. Line numbers are
+/// 1-indexed.
+#[derive(Debug)]
+pub enum CheckpointError {
+    Io(io::Error),
+    MissingHeader,
+    InvalidField { line: usize, field: String },
+    MissingField(&'static str),
+}
+
+impl Display for CheckpointError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{}", e),
+            Self::MissingHeader => write!(f,
+                "not a checkpoint: expected {:?} on line 1", HEADER),
+            Self::InvalidField { line, field } => write!(f,
+                "line {}: invalid field {:?}", line, field),
+            Self::MissingField(key) => write!(f, "missing field {:?}", key),
+        }
+    }
+}
+
+impl Error for CheckpointError {}
+
+impl From<io::Error> for CheckpointError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl Display for Checkpoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", HEADER)?;
+        writeln!(f, "ip {}", self.ip)?;
+        writeln!(f, "relative-base {}", self.relative_base)?;
+        writeln!(f, "steps {}", self.steps)?;
+        writeln!(f, "memory-limit {}", self.memory_limit)?;
+        writeln!(f, "instruction-set {}", set_name(self.instruction_set))?;
+        list(f, "input", &self.input)?;
+        list(f, "output", &self.output)?;
+        list(f, "memory", &self.memory)
+    }
+}
+
+impl FromStr for Checkpoint {
+    type Err = CheckpointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        if Some(HEADER) != lines.next().map(str::trim) {
+            return Err(CheckpointError::MissingHeader);
+        }
+        let mut ip = None;
+        let mut relative_base = None;
+        let mut steps = None;
+        let mut memory_limit = None;
+        let mut instruction_set = None;
+        let mut input = None;
+        let mut output = None;
+        let mut memory = None;
+        for (index, text) in lines.enumerate() {
+            let text = text.trim();
+            if text.is_empty() {
+                continue;
+            }
+            let invalid = || CheckpointError::InvalidField {
+                line: index + 2,
+                field: text.to_string(),
+            };
+            let (key, value) = match text.find(' ') {
+                Some(i) => (&text[..i], text[i + 1..].trim()),
+                None => (text, ""),
+            };
+            match key {
+                "ip" => ip = Some(value.parse().map_err(|_| invalid())?),
+                "relative-base" => {
+                    let value = value.parse().map_err(|_| invalid())?;
+                    relative_base = Some(value);
+                },
+                "steps" => steps = Some(value.parse().map_err(|_| invalid())?),
+                "memory-limit" => {
+                    memory_limit = Some(value.parse().map_err(|_| invalid())?);
+                },
+                "instruction-set" => instruction_set = match value {
+                    "day2" => Some(InstructionSet::Day2),
+                    "day5" => Some(InstructionSet::Day5),
+                    "day9" => Some(InstructionSet::Day9),
+                    _ => return Err(invalid()),
+                },
+                "input" => input = Some(split(value).ok_or_else(invalid)?),
+                "output" => output = Some(split(value).ok_or_else(invalid)?),
+                "memory" => memory = Some(split(value).ok_or_else(invalid)?),
+                _ => return Err(invalid()),
+            }
+        }
+        Ok(Self {
+            memory: memory.ok_or(CheckpointError::MissingField("memory"))?,
+            ip: ip.ok_or(CheckpointError::MissingField("ip"))?,
+            relative_base: relative_base
+                .ok_or(CheckpointError::MissingField("relative-base"))?,
+            steps: steps.ok_or(CheckpointError::MissingField("steps"))?,
+            memory_limit: memory_limit
+                .ok_or(CheckpointError::MissingField("memory-limit"))?,
+            instruction_set: instruction_set
+                .ok_or(CheckpointError::MissingField("instruction-set"))?,
+            input: input.ok_or(CheckpointError::MissingField("input"))?,
+            output: output.ok_or(CheckpointError::MissingField("output"))?,
+        })
+    }
+}
+
+impl Checkpoint {
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, CheckpointError> {
+        fs::read_to_string(path)?.parse()
+    }
+
+    /// Every way `other` differs from this checkpoint. Memory is compared
+    /// cell by cell, with cells past the end of the shorter memory read as 0
+    /// like the machine itself would.
+    pub fn diff(&self, other: &Checkpoint) -> Vec<Difference> {
+        let mut differences = Vec::new();
+        let mut field = |name, old: String, new: String| {
+            if old != new {
+                differences.push(Difference::Field { name, old, new });
+            }
+        };
+        field("ip", self.ip.to_string(), other.ip.to_string());
+        field("relative-base", self.relative_base.to_string(),
+            other.relative_base.to_string());
+        field("steps", self.steps.to_string(), other.steps.to_string());
+        field("memory-limit", self.memory_limit.to_string(),
+            other.memory_limit.to_string());
+        field("instruction-set", set_name(self.instruction_set).to_string(),
+            set_name(other.instruction_set).to_string());
+        field("input", join(&self.input), join(&other.input));
+        field("output", join(&self.output), join(&other.output));
+        let len = self.memory.len().max(other.memory.len());
+        for address in 0..len {
+            let old = self.memory.get(address).copied().unwrap_or(0);
+            let new = other.memory.get(address).copied().unwrap_or(0);
+            if old != new {
+                differences.push(Difference::Memory { address, old, new });
+            }
+        }
+        differences
+    }
+}
+
+/// One way two checkpoints differ.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Difference {
+    /// A register, counter, setting, or queue, formatted as in the file.
+    Field { name: &'static str, old: String, new: String },
+    Memory { address: usize, old: i64, new: i64 },
+}
+
+impl Display for Difference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Field { name, old, new } => write!(f,
+                "{}: {} -> {}", name, old, new),
+            Self::Memory { address, old, new } => write!(f,
+                "mem[{}]: {} -> {}", address, old, new),
+        }
+    }
+}
+
+fn set_name(instruction_set: InstructionSet) -> &'static str {
+    match instruction_set {
+        InstructionSet::Day2 => "day2",
+        InstructionSet::Day5 => "day5",
+        InstructionSet::Day9 => "day9",
+    }
+}
+
+fn list(f: &mut Formatter<'_>, key: &str, values: &[i64]) -> fmt::Result {
+    if values.is_empty() {
+        writeln!(f, "{}", key)
+    } else {
+        writeln!(f, "{} {}", key, join(values))
+    }
+}
+
+fn join(values: &[i64]) -> String {
+    values
+        .iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn split(s: &str) -> Option<Vec<i64>> {
+    if s.is_empty() {
+        return Some(Vec::new());
+    }
+    s.split(',').map(|value| value.trim().parse().ok()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::{Intcode, RunState};
+
+    // Output whether the input equals 8.
+    const EQUALS_8: [i64; 11] = [3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8];
+
+    #[test]
+    fn round_trips_and_resumes() {
+        let mut vm = Intcode::new(EQUALS_8.to_vec());
+        vm.push_input(8);
+        vm.push_input(3);
+        vm.step().unwrap();
+        let checkpoint = vm.checkpoint();
+        let text = checkpoint.to_string();
+        assert_eq!("intcode-checkpoint 1\nip 2\nrelative-base 0\nsteps 1\n\
+            memory-limit 16777216\ninstruction-set day9\ninput 3\noutput\n\
+            memory 3,9,8,9,10,9,4,9,99,8,8\n", text);
+        let parsed: Checkpoint = text.parse().unwrap();
+        assert_eq!(checkpoint, parsed);
+        let mut resumed = Intcode::from_checkpoint(&parsed);
+        assert_eq!(Ok(RunState::Output(1)), resumed.run());
+        assert_eq!(Ok(RunState::Output(1)), vm.run());
+        assert_eq!(Ok(RunState::Halted), resumed.run());
+        assert_eq!(vm.checkpoint(), resumed.checkpoint());
+    }
+
+    #[test]
+    fn diffs_fields_and_memory() {
+        let mut vm = Intcode::new(EQUALS_8.to_vec());
+        let before = vm.checkpoint();
+        vm.push_input(7);
+        assert_eq!(Ok(vec![0]), vm.run_to_halt());
+        let differences: Vec<String> = before
+            .diff(&vm.checkpoint())
+            .iter()
+            .map(|difference| difference.to_string())
+            .collect();
+        assert_eq!(vec!["ip: 0 -> 8", "steps: 0 -> 3", "mem[9]: -1 -> 0"],
+            differences);
+    }
+
+    #[test]
+    fn reports_bad_files() {
+        assert!(matches!("ip 0".parse::<Checkpoint>(),
+            Err(CheckpointError::MissingHeader)));
+        assert!(matches!("intcode-checkpoint 1\nip x".parse::<Checkpoint>(),
+            Err(CheckpointError::InvalidField { line: 2, .. })));
+        assert!(matches!("intcode-checkpoint 1\nip 0".parse::<Checkpoint>(),
+            Err(CheckpointError::MissingField("memory"))));
+    }
+}
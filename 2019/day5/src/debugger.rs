@@ -0,0 +1,385 @@
+use std::{
+    collections::{BTreeSet, VecDeque},
+    io::{self, Write},
+    path::Path,
+};
+
+use crate::{
+    checkpoint::Checkpoint,
+    error::IntcodeError,
+    instruction::Instruction,
+    trace::{MemoryWrite, TraceEvent},
+    vm::{Intcode, RunState},
+};
+
+/// Why `Debugger::continue_execution` gave control back.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Stop {
+    Breakpoint(usize),
+    Watchpoint(MemoryWrite),
+    Halted,
+    NeedsInput,
+}
+
+/// An interactive wrapper around an `Intcode` machine with breakpoints,
+/// watchpoints, and a bounded history of executed steps that can be undone.
+#[derive(Debug)]
+pub struct Debugger {
+    vm: Intcode,
+    breakpoints: BTreeSet<usize>,
+    watchpoints: BTreeSet<usize>,
+    history: VecDeque<TraceEvent>,
+    history_limit: usize,
+    outputs: Vec<i64>,
+}
+
+const HELP: &str = "\
+Commands:
+  step [n]         (s)  execute n instructions (default 1)
+  continue         (c)  run until a breakpoint, watchpoint, halt, or input
+  break <addr>     (b)  break when ip reaches addr
+  delete <addr>    (d)  remove a breakpoint
+  watch <addr>     (w)  stop when mem[addr] is written
+  unwatch <addr>        remove a watchpoint
+  print mem[a..b]  (p)  print memory (also mem[a])
+  input <v>...     (i)  queue input values
+  rewind [n]       (r)  undo the last n instructions (default 1)
+  save <file>           checkpoint the machine to a file
+  load <file>           resume from a checkpoint, forgetting the history
+  regs                  show ip, relative base, and the next instruction
+  output                show every value output so far
+  quit             (q)";
+
+impl Debugger {
+    /// Wrap a machine, remembering up to `history_limit` steps for `rewind`.
+    pub fn new(vm: Intcode, history_limit: usize) -> Self {
+        Self {
+            vm,
+            breakpoints: BTreeSet::new(),
+            watchpoints: BTreeSet::new(),
+            history: VecDeque::new(),
+            history_limit,
+            outputs: Vec::new(),
+        }
+    }
+
+    pub fn vm(&self) -> &Intcode {
+        &self.vm
+    }
+
+    pub fn vm_mut(&mut self) -> &mut Intcode {
+        &mut self.vm
+    }
+
+    /// Every value the machine has output, oldest first.
+    pub fn outputs(&self) -> &[i64] {
+        &self.outputs
+    }
+
+    pub fn add_breakpoint(&mut self, address: usize) {
+        self.breakpoints.insert(address);
+    }
+
+    pub fn remove_breakpoint(&mut self, address: usize) {
+        self.breakpoints.remove(&address);
+    }
+
+    pub fn add_watchpoint(&mut self, address: usize) {
+        self.watchpoints.insert(address);
+    }
+
+    pub fn remove_watchpoint(&mut self, address: usize) {
+        self.watchpoints.remove(&address);
+    }
+
+    /// Execute one instruction. Returns `None` if the machine is halted or
+    /// waiting for input, since nothing was executed.
+    pub fn step(&mut self) -> Result<Option<TraceEvent>, IntcodeError> {
+        let (state, event) = self.vm.step_traced()?;
+        match state {
+            Some(RunState::Halted)
+                    | Some(RunState::NeedsInput)
+                    | Some(RunState::BudgetExhausted) => return Ok(None),
+            Some(RunState::Output(value)) => self.outputs.push(value),
+            None => (),
+        }
+        if self.history_limit <= self.history.len() {
+            self.history.pop_front();
+        }
+        if 0 < self.history_limit {
+            self.history.push_back(event.clone());
+        }
+        Ok(Some(event))
+    }
+
+    /// Keep stepping until something interesting happens. At least one
+    /// instruction is executed, so continuing from a breakpoint moves on.
+    pub fn continue_execution(&mut self) -> Result<Stop, IntcodeError> {
+        loop {
+            let event = match self.step()? {
+                Some(event) => event,
+                None => return Ok(self.blocked_reason()),
+            };
+            if let Some(write) = event.write {
+                if self.watchpoints.contains(&write.address) {
+                    return Ok(Stop::Watchpoint(write));
+                }
+            }
+            if self.breakpoints.contains(&self.vm.ip()) {
+                return Ok(Stop::Breakpoint(self.vm.ip()));
+            }
+        }
+    }
+
+    /// Undo up to `n` of the most recent steps, returning how many were
+    /// undone. Memory grown by the undone steps stays allocated.
+    pub fn rewind(&mut self, n: usize) -> usize {
+        let mut undone = 0;
+        while undone < n {
+            let event = match self.history.pop_back() {
+                Some(event) => event,
+                None => break,
+            };
+            if let Some(MemoryWrite { address, old, new }) = event.write {
+                self.vm.memory_mut()[address] = old;
+                if let Instruction::Input(_) = event.instruction {
+                    self.vm.unread_input(new);
+                }
+            }
+            if let Instruction::Output(_) = event.instruction {
+                self.outputs.pop();
+            }
+            self.vm.restore_registers(event.ip, event.relative_base);
+            undone += 1;
+        }
+        undone
+    }
+
+    /// Run one line of debugger command text, writing any results to `out`.
+    /// Returns `false` once the user asks to quit.
+    pub fn command<W: Write>(&mut self, line: &str, out: &mut W)
+            -> io::Result<bool> {
+        let mut words = line.split_whitespace();
+        let command = match words.next() {
+            Some(command) => command,
+            None => return Ok(true),
+        };
+        let args: Vec<&str> = words.collect();
+        match command {
+            "s" | "step" => {
+                let n = match parse_count(&args) {
+                    Some(n) => n,
+                    None => return usage(out, "step [n]"),
+                };
+                for _ in 0..n {
+                    match self.step() {
+                        Ok(Some(event)) => writeln!(out, "{}", event)?,
+                        Ok(None) => {
+                            let reason = self.blocked_reason();
+                            writeln!(out, "{}", describe(&reason))?;
+                            break;
+                        },
+                        Err(e) => {
+                            writeln!(out, "error: {}", e)?;
+                            break;
+                        },
+                    }
+                }
+            },
+            "c" | "continue" => match self.continue_execution() {
+                Ok(stop) => writeln!(out, "{}", describe(&stop))?,
+                Err(e) => writeln!(out, "error: {}", e)?,
+            },
+            "b" | "break" | "d" | "delete" | "w" | "watch" | "unwatch" => {
+                let address = match args.as_slice() {
+                    [address] => address.parse().ok(),
+                    _ => None,
+                };
+                let address = match address {
+                    Some(address) => address,
+                    None => return usage(out, &format!("{} <addr>", command)),
+                };
+                match command {
+                    "b" | "break" => self.add_breakpoint(address),
+                    "d" | "delete" => self.remove_breakpoint(address),
+                    "w" | "watch" => self.add_watchpoint(address),
+                    _ => self.remove_watchpoint(address),
+                }
+            },
+            "p" | "print" => {
+                let range = match args.as_slice() {
+                    [range] => parse_memory_range(range),
+                    _ => None,
+                };
+                let (start, end) = match range {
+                    Some(range) => range,
+                    None => return usage(out, "print mem[a..b]"),
+                };
+                let mem = self.vm.memory();
+                for address in start..end {
+                    let value = mem.get(address).copied().unwrap_or(0);
+                    writeln!(out, "mem[{}] = {}", address, value)?;
+                }
+            },
+            "i" | "input" => {
+                let values: Result<Vec<i64>, _> = args
+                    .iter()
+                    .map(|arg| arg.parse())
+                    .collect();
+                match values {
+                    Ok(values) if !values.is_empty() => {
+                        for value in values {
+                            self.vm.push_input(value);
+                        }
+                    },
+                    _ => return usage(out, "input <v>..."),
+                }
+            },
+            "r" | "rewind" => {
+                let n = match parse_count(&args) {
+                    Some(n) => n,
+                    None => return usage(out, "rewind [n]"),
+                };
+                let undone = self.rewind(n);
+                writeln!(out, "rewound {} step(s) to ip {}",
+                    undone, self.vm.ip())?;
+            },
+            "save" | "load" => {
+                let path = match args.as_slice() {
+                    [path] => Path::new(path),
+                    _ => return usage(out, &format!("{} <file>", command)),
+                };
+                if "save" == command {
+                    match self.vm.checkpoint().save(path) {
+                        Ok(()) => writeln!(out, "saved {}", path.display())?,
+                        Err(e) => writeln!(out, "error: {}", e)?,
+                    }
+                } else {
+                    match Checkpoint::load(path) {
+                        Ok(checkpoint) => {
+                            self.vm.restore(&checkpoint);
+                            self.history.clear();
+                            writeln!(out, "loaded {} at ip {}",
+                                path.display(), self.vm.ip())?;
+                        },
+                        Err(e) => writeln!(out, "error: {}", e)?,
+                    }
+                }
+            },
+            "regs" => {
+                let next = match Instruction::decode(self.vm.memory(),
+                        self.vm.ip()) {
+                    Ok(instruction) => instruction.to_string(),
+                    Err(e) => format!("<{}>", e),
+                };
+                writeln!(out, "ip = {}, rb = {}, next: {}",
+                    self.vm.ip(), self.vm.relative_base(), next)?;
+            },
+            "output" => writeln!(out, "{:?}", self.outputs)?,
+            "h" | "help" => writeln!(out, "{}", HELP)?,
+            "q" | "quit" => return Ok(false),
+            _ => writeln!(out, "unknown command {:?}; try `help`", command)?,
+        }
+        Ok(true)
+    }
+
+    // Stepping a halted or starved machine is a no-op, so find out which.
+    fn blocked_reason(&self) -> Stop {
+        match Instruction::decode(self.vm.memory(), self.vm.ip()) {
+            Ok(Instruction::Halt) => Stop::Halted,
+            _ => Stop::NeedsInput,
+        }
+    }
+}
+
+fn describe(stop: &Stop) -> String {
+    match stop {
+        Stop::Breakpoint(address) => format!("breakpoint at {}", address),
+        Stop::Watchpoint(MemoryWrite { address, old, new }) => {
+            format!("watchpoint: mem[{}]: {} -> {}", address, old, new)
+        },
+        Stop::Halted => "halted".to_string(),
+        Stop::NeedsInput => "waiting for input".to_string(),
+    }
+}
+
+fn usage<W: Write>(out: &mut W, usage: &str) -> io::Result<bool> {
+    writeln!(out, "usage: {}", usage)?;
+    Ok(true)
+}
+
+fn parse_count(args: &[&str]) -> Option<usize> {
+    match args {
+        [] => Some(1),
+        [n] => n.parse().ok(),
+        _ => None,
+    }
+}
+
+// Parse `mem[a..b]` (exclusive) or `mem[a]`.
+fn parse_memory_range(s: &str) -> Option<(usize, usize)> {
+    let inner = s.strip_prefix("mem[")?.strip_suffix(']')?;
+    match inner.find("..") {
+        Some(i) => {
+            let start = inner[..i].parse().ok()?;
+            let end = inner[i + 2..].parse().ok()?;
+            Some((start, end))
+        },
+        None => {
+            let address: usize = inner.parse().ok()?;
+            Some((address, address.checked_add(1)?))
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Read a value, double it twice, output it, and halt.
+    fn debugger() -> Debugger {
+        let program = vec![
+            3, 13, 1002, 13, 2, 13, 1002, 13, 2, 13, 4, 13, 99, 0,
+        ];
+        Debugger::new(Intcode::new(program), 100)
+    }
+
+    fn run(debugger: &mut Debugger, commands: &[&str]) -> String {
+        let mut out = Vec::new();
+        for command in commands {
+            debugger.command(command, &mut out).unwrap();
+        }
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn breakpoints_and_watchpoints() {
+        let mut debugger = debugger();
+        let commands = ["c", "input 5", "b 6", "c", "w 13", "c"];
+        let out = run(&mut debugger, &commands);
+        assert_eq!("waiting for input\nbreakpoint at 6\n\
+            watchpoint: mem[13]: 10 -> 20\n", out);
+        let out = run(&mut debugger, &["c", "output", "p mem[13..14]"]);
+        assert_eq!("halted\n[20]\nmem[13] = 20\n", out);
+    }
+
+    #[test]
+    fn rejects_memory_past_the_last_address() {
+        let out = run(&mut debugger(), &["p mem[18446744073709551615]"]);
+        assert_eq!("usage: print mem[a..b]\n", out);
+    }
+
+    #[test]
+    fn rewind_undoes_writes_input_and_output() {
+        let mut debugger = debugger();
+        debugger.vm_mut().push_input(5);
+        assert_eq!(Ok(Stop::Halted), debugger.continue_execution());
+        assert_eq!(&[20], debugger.outputs());
+        assert_eq!(4, debugger.rewind(10));
+        assert_eq!(0, debugger.vm().ip());
+        assert_eq!(0, debugger.vm().memory()[13]);
+        assert!(debugger.outputs().is_empty());
+        assert_eq!(Ok(Stop::Halted), debugger.continue_execution());
+        assert_eq!(&[20], debugger.outputs());
+    }
+}
@@ -0,0 +1,329 @@
+use std::{
+    collections::VecDeque,
+    convert::TryFrom,
+    error::Error,
+    fmt::{Display, Formatter, self},
+    io::{self, BufRead, Stdout, Write},
+    sync::mpsc::{Receiver, Sender},
+};
+
+/// Something an Intcode machine can read input values from.
+pub trait InputDevice {
+    /// The next input value, or `None` if there isn't one (yet). A machine
+    /// reading `None` pauses with `RunState::NeedsInput`. An error wrapping
+    /// `UnparsableInput` is reported as `IntcodeError::UnparsableInput`, and
+    /// any other as `IntcodeError::Device`.
+    fn read(&mut self) -> io::Result<Option<i64>>;
+}
+
+/// Text an input device read that isn't an integer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnparsableInput(pub String);
+
+impl Display for UnparsableInput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot parse input {:?} as an integer", self.0)
+    }
+}
+
+impl Error for UnparsableInput {}
+
+impl From<UnparsableInput> for io::Error {
+    fn from(e: UnparsableInput) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+/// Something an Intcode machine can write output values to.
+pub trait OutputDevice {
+    fn write(&mut self, value: i64) -> io::Result<()>;
+}
+
+impl InputDevice for VecDeque<i64> {
+    fn read(&mut self) -> io::Result<Option<i64>> {
+        Ok(self.pop_front())
+    }
+}
+
+impl OutputDevice for VecDeque<i64> {
+    fn write(&mut self, value: i64) -> io::Result<()> {
+        self.push_back(value);
+        Ok(())
+    }
+}
+
+impl OutputDevice for Vec<i64> {
+    fn write(&mut self, value: i64) -> io::Result<()> {
+        self.push(value);
+        Ok(())
+    }
+}
+
+/// Blocks until a value arrives. Reads `None` once every sender is gone.
+impl InputDevice for Receiver<i64> {
+    fn read(&mut self) -> io::Result<Option<i64>> {
+        Ok(self.recv().ok())
+    }
+}
+
+impl OutputDevice for Sender<i64> {
+    fn write(&mut self, value: i64) -> io::Result<()> {
+        self.send(value).map_err(|_| {
+            io::Error::new(io::ErrorKind::BrokenPipe, "receiver hung up")
+        })
+    }
+}
+
+/// Input drawn from any iterator of values.
+#[derive(Clone, Debug)]
+pub struct IterInput<I>(pub I);
+
+impl<I: Iterator<Item = i64>> InputDevice for IterInput<I> {
+    fn read(&mut self) -> io::Result<Option<i64>> {
+        Ok(self.0.next())
+    }
+}
+
+/// Input that sends text one character code at a time.
+#[derive(Clone, Debug, Default)]
+pub struct AsciiInput {
+    queue: VecDeque<i64>,
+}
+
+impl AsciiInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue `text` followed by a newline.
+    pub fn push_line(&mut self, text: &str) {
+        self.push_str(text);
+        self.queue.push_back(i64::from(b'\n'));
+    }
+
+    pub fn push_str(&mut self, text: &str) {
+        self.queue.extend(text.bytes().map(i64::from));
+    }
+}
+
+impl InputDevice for AsciiInput {
+    fn read(&mut self) -> io::Result<Option<i64>> {
+        Ok(self.queue.pop_front())
+    }
+}
+
+/// Output that collects character codes back into lines of text. Values
+/// outside the ASCII range, typically a program's final answer, are kept
+/// apart as numbers.
+#[derive(Clone, Debug, Default)]
+pub struct AsciiOutput {
+    lines: Vec<String>,
+    partial: String,
+    values: Vec<i64>,
+}
+
+impl AsciiOutput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every complete line received so far, without newlines.
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Take the complete lines received so far.
+    pub fn take_lines(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.lines)
+    }
+
+    /// The text received since the last newline, such as a prompt.
+    pub fn partial(&self) -> &str {
+        &self.partial
+    }
+
+    /// Every value received that isn't an ASCII character code.
+    pub fn values(&self) -> &[i64] {
+        &self.values
+    }
+}
+
+impl OutputDevice for AsciiOutput {
+    fn write(&mut self, value: i64) -> io::Result<()> {
+        match ascii(value) {
+            Some('\n') => self.lines.push(std::mem::take(&mut self.partial)),
+            Some(c) => self.partial.push(c),
+            None => self.values.push(value),
+        }
+        Ok(())
+    }
+}
+
+/// How a terminal adapter turns text into values and back.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Encoding {
+    /// One integer per line.
+    Numeric,
+    /// Character codes. Output values outside the ASCII range are printed as
+    /// numbers on their own line.
+    Ascii,
+}
+
+/// Input read a line at a time from a reader, such as stdin.
+#[derive(Debug)]
+pub struct TerminalInput<R> {
+    reader: R,
+    encoding: Encoding,
+    pending: VecDeque<i64>,
+}
+
+impl<R: BufRead> TerminalInput<R> {
+    pub fn new(reader: R, encoding: Encoding) -> Self {
+        Self { reader, encoding, pending: VecDeque::new() }
+    }
+}
+
+impl TerminalInput<io::StdinLock<'static>> {
+    pub fn stdin(encoding: Encoding) -> Self {
+        Self::new(io::stdin().lock(), encoding)
+    }
+}
+
+impl<R: BufRead> InputDevice for TerminalInput<R> {
+    /// Reads `None` at end of file.
+    fn read(&mut self) -> io::Result<Option<i64>> {
+        if let Some(value) = self.pending.pop_front() {
+            return Ok(Some(value));
+        }
+        let mut line = String::new();
+        if 0 == self.reader.read_line(&mut line)? {
+            return Ok(None);
+        }
+        match self.encoding {
+            Encoding::Numeric => match line.trim().parse() {
+                Ok(value) => Ok(Some(value)),
+                Err(_) => Err(UnparsableInput(line.trim().to_string()).into()),
+            },
+            Encoding::Ascii => {
+                // Programs expect a bare newline whatever the platform sends.
+                let text = line.trim_end_matches(['\r', '\n']);
+                self.pending.extend(text.bytes().map(i64::from));
+                self.pending.push_back(i64::from(b'\n'));
+                Ok(self.pending.pop_front())
+            },
+        }
+    }
+}
+
+/// Output written as text to a writer, such as stdout. The writer is
+/// flushed after every value so prompts show up before input is read.
+#[derive(Debug)]
+pub struct TerminalOutput<W> {
+    writer: W,
+    encoding: Encoding,
+}
+
+impl<W: Write> TerminalOutput<W> {
+    pub fn new(writer: W, encoding: Encoding) -> Self {
+        Self { writer, encoding }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl TerminalOutput<Stdout> {
+    pub fn stdout(encoding: Encoding) -> Self {
+        Self::new(io::stdout(), encoding)
+    }
+}
+
+impl<W: Write> OutputDevice for TerminalOutput<W> {
+    fn write(&mut self, value: i64) -> io::Result<()> {
+        match (self.encoding, ascii(value)) {
+            (Encoding::Ascii, Some(c)) => write!(self.writer, "{}", c)?,
+            _ => writeln!(self.writer, "{}", value)?,
+        }
+        self.writer.flush()
+    }
+}
+
+fn ascii(value: i64) -> Option<char> {
+    u8::try_from(value)
+        .ok()
+        .filter(u8::is_ascii)
+        .map(char::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{sync::mpsc, thread};
+    use crate::{error::IntcodeError, vm::{Intcode, RunState}};
+
+    // Echo every input value until reading a 0, then halt.
+    const ECHO: [i64; 12] = [3, 11, 1006, 11, 10, 4, 11, 1105, 1, 0, 99, 0];
+
+    #[test]
+    fn queues_and_iterators() {
+        let mut vm = Intcode::new(ECHO.to_vec());
+        let mut input: VecDeque<i64> = vec![1, 2].into();
+        let mut output = Vec::new();
+        assert_eq!(Ok(RunState::NeedsInput),
+            vm.run_with(&mut input, &mut output));
+        let mut input = IterInput(vec![3, 0].into_iter());
+        assert_eq!(Ok(RunState::Halted), vm.run_with(&mut input, &mut output));
+        assert_eq!(vec![1, 2, 3], output);
+    }
+
+    #[test]
+    fn channels_connect_threads() {
+        let (to_vm, mut input) = mpsc::channel();
+        let (mut output, from_vm) = mpsc::channel();
+        let worker = thread::spawn(move || {
+            let mut vm = Intcode::new(ECHO.to_vec());
+            vm.run_with(&mut input, &mut output)
+        });
+        for value in [5, 6, 0] {
+            to_vm.send(value).unwrap();
+        }
+        assert_eq!(Ok(RunState::Halted), worker.join().unwrap());
+        assert_eq!(vec![5, 6], from_vm.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn ascii_lines() {
+        let mut vm = Intcode::new(ECHO.to_vec());
+        let mut input = AsciiInput::new();
+        input.push_line("hi");
+        input.push_str("ok?");
+        let mut output = AsciiOutput::new();
+        assert_eq!(Ok(RunState::NeedsInput),
+            vm.run_with(&mut input, &mut output));
+        vm.push_input(1000);
+        vm.push_input(0);
+        assert_eq!(Ok(RunState::Halted), vm.run_with(&mut input, &mut output));
+        assert_eq!(&["hi".to_string()], output.lines());
+        assert_eq!("ok?", output.partial());
+        assert_eq!(&[1000], output.values());
+    }
+
+    #[test]
+    fn terminal() {
+        let mut vm = Intcode::new(ECHO.to_vec());
+        let mut input = TerminalInput::new(&b"hi\r\n"[..], Encoding::Ascii);
+        let mut output = TerminalOutput::new(Vec::new(), Encoding::Ascii);
+        assert_eq!(Ok(RunState::NeedsInput),
+            vm.run_with(&mut input, &mut output));
+        let mut input = TerminalInput::new(&b"7\nx\n"[..], Encoding::Numeric);
+        let mut numbers = TerminalOutput::new(Vec::new(), Encoding::Numeric);
+        assert_eq!(Err(IntcodeError::UnparsableInput {
+            ip: 0,
+            opcode: 3,
+            input: "x".to_string(),
+        }), vm.run_with(&mut input, &mut numbers));
+        assert_eq!(b"hi\n".to_vec(), output.into_inner());
+        assert_eq!(b"7\n".to_vec(), numbers.into_inner());
+    }
+}
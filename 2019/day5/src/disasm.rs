@@ -0,0 +1,124 @@
+use std::fmt::{Display, Formatter, self};
+
+use crate::instruction::{Instruction, Operand};
+
+/// What a run of words in a program image was decoded as.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Item {
+    Code(Instruction),
+    /// A word that doesn't begin a well-formed instruction.
+    Data(i64),
+}
+
+/// One line of a disassembly listing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Line {
+    pub address: usize,
+    pub words: Vec<i64>,
+    pub item: Item,
+}
+
+impl Display for Operand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            Operand::Address(p) => write!(f, "[{}]", p),
+            Operand::Immediate(v) => write!(f, "#{}", v),
+            Operand::Relative(offset) if offset < 0 => {
+                write!(f, "[rb-{}]", -i128::from(offset))
+            },
+            Operand::Relative(offset) => write!(f, "[rb+{}]", offset),
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.mnemonic())?;
+        for (i, op) in self.operands().iter().enumerate() {
+            let separator = if 0 == i { " " } else { ", " };
+            write!(f, "{}{}", separator, op)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Line {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let words = self.words
+            .iter()
+            .map(|word| word.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        write!(f, "{:>6}: {:<20} ", self.address, words)?;
+        match self.item {
+            Item::Code(instruction) => write!(f, "{}", instruction),
+            Item::Data(value) => write!(f, "DATA {}", value),
+        }
+    }
+}
+
+/// Walk a program image from address 0, decoding each word as the start of
+/// an instruction. Words that can't be decoded, that begin an instruction
+/// running off the end of the image, or that begin an instruction writing
+/// through an immediate operand, are marked as data.
+pub fn disassemble(program: &[i64]) -> Vec<Line> {
+    let mut lines = Vec::new();
+    let mut address = 0;
+    while address < program.len() {
+        let item = match Instruction::decode(program, address) {
+            Ok(i) if is_well_formed(&i, program, address) => Item::Code(i),
+            _ => Item::Data(program[address]),
+        };
+        let size = match item {
+            Item::Code(instruction) => instruction.size(),
+            Item::Data(_) => 1,
+        };
+        lines.push(Line {
+            address,
+            words: program[address..address + size].to_vec(),
+            item,
+        });
+        address += size;
+    }
+    lines
+}
+
+/// Disassemble a program image into a printable listing, one line per
+/// instruction or data word.
+pub fn listing(program: &[i64]) -> String {
+    disassemble(program)
+        .iter()
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+fn is_well_formed(instruction: &Instruction, program: &[i64], address: usize)
+        -> bool {
+    let fits = address + instruction.size() <= program.len();
+    let writable = !matches!(instruction.destination(),
+        Some(Operand::Immediate(_)));
+    fits && writable
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn listing_shows_modes_and_data() {
+        let program = [1002, 4, 3, 4, 109, -2, 204, 1, 11101, 0, 0, 0, 99, 7];
+        let expected = [
+            "     0: 1002,4,3,4           MUL [4], #3, [4]",
+            "     4: 109,-2               ARB #-2",
+            "     6: 204,1                OUT [rb+1]",
+            "     8: 11101                DATA 11101",
+            "     9: 0                    DATA 0",
+            "    10: 0                    DATA 0",
+            "    11: 0                    DATA 0",
+            "    12: 99                   HLT",
+            "    13: 7                    DATA 7",
+        ];
+        let listing = listing(&program);
+        assert_eq!(expected.to_vec(), listing.lines().collect::<Vec<_>>());
+    }
+}
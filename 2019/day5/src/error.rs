@@ -0,0 +1,94 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, self},
+};
+
+/// Everything that can go wrong while running an Intcode program. Each
+/// variant records the instruction pointer and the raw opcode word of the
+/// instruction that failed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum IntcodeError {
+    /// The low two digits of the opcode word don't name an instruction.
+    UnknownOpcode { ip: usize, opcode: i64 },
+    /// A parameter mode digit isn't 0, 1, or 2. Parameters are 1-indexed.
+    InvalidParameterMode { ip: usize, opcode: i64, parameter: u8, mode: i64 },
+    /// An instruction tried to write through an immediate-mode parameter.
+    ImmediateModeWrite { ip: usize, opcode: i64 },
+    /// An address lies beyond the machine's memory limit.
+    AddressOutOfBounds { ip: usize, opcode: i64, address: i64 },
+    /// An address (or jump target) is negative.
+    NegativeAddress { ip: usize, opcode: i64, address: i64 },
+    /// Text given to an Input instruction isn't an integer.
+    UnparsableInput { ip: usize, opcode: i64, input: String },
+    /// An Input instruction ran out of values to read.
+    InputExhausted { ip: usize, opcode: i64 },
+    /// An input or output device failed while serving an instruction.
+    Device { ip: usize, opcode: i64, message: String },
+    /// The machine used up its step limit before halting.
+    BudgetExhausted { ip: usize, opcode: i64, limit: u64 },
+    /// Loop detection saw the machine return to an earlier state, so it can
+    /// never halt. The state after step `start` came back `length` steps
+    /// later, with `ip` pointing at the instruction shown.
+    InfiniteLoop { ip: usize, opcode: i64, start: u64, length: u64 },
+}
+
+impl IntcodeError {
+    pub fn ip(&self) -> usize {
+        match *self {
+            Self::UnknownOpcode { ip, .. }
+            | Self::InvalidParameterMode { ip, .. }
+            | Self::ImmediateModeWrite { ip, .. }
+            | Self::AddressOutOfBounds { ip, .. }
+            | Self::NegativeAddress { ip, .. }
+            | Self::UnparsableInput { ip, .. }
+            | Self::InputExhausted { ip, .. }
+            | Self::Device { ip, .. }
+            | Self::BudgetExhausted { ip, .. }
+            | Self::InfiniteLoop { ip, .. } => ip,
+        }
+    }
+
+    pub fn opcode(&self) -> i64 {
+        match *self {
+            Self::UnknownOpcode { opcode, .. }
+            | Self::InvalidParameterMode { opcode, .. }
+            | Self::ImmediateModeWrite { opcode, .. }
+            | Self::AddressOutOfBounds { opcode, .. }
+            | Self::NegativeAddress { opcode, .. }
+            | Self::UnparsableInput { opcode, .. }
+            | Self::InputExhausted { opcode, .. }
+            | Self::Device { opcode, .. }
+            | Self::BudgetExhausted { opcode, .. }
+            | Self::InfiniteLoop { opcode, .. } => opcode,
+        }
+    }
+}
+
+impl Display for IntcodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "ip {} (opcode {}): ", self.ip(), self.opcode())?;
+        match self {
+            Self::UnknownOpcode { .. } => write!(f, "unknown opcode"),
+            Self::InvalidParameterMode { parameter, mode, .. } => write!(f,
+                "invalid mode {} for parameter {}", mode, parameter),
+            Self::ImmediateModeWrite { .. } => write!(f,
+                "cannot write through an immediate-mode parameter"),
+            Self::AddressOutOfBounds { address, .. } => write!(f,
+                "address {} is out of bounds", address),
+            Self::NegativeAddress { address, .. } => write!(f,
+                "address {} is negative", address),
+            Self::UnparsableInput { input, .. } => write!(f,
+                "cannot parse input {:?} as an integer", input),
+            Self::InputExhausted { .. } => write!(f, "input exhausted"),
+            Self::Device { message, .. } => write!(f,
+                "device failed: {}", message),
+            Self::BudgetExhausted { limit, .. } => write!(f,
+                "step limit of {} exhausted", limit),
+            Self::InfiniteLoop { start, length, .. } => write!(f,
+                "infinite loop: the state after step {} repeats every {} \
+                steps", start, length),
+        }
+    }
+}
+
+impl Error for IntcodeError {}
@@ -0,0 +1,268 @@
+use std::fmt::{Display, Formatter, self};
+
+use crate::{
+    checkpoint::Checkpoint,
+    error::IntcodeError,
+    instruction::{Instruction, Operand},
+    vm::{Engine, Intcode, RunState},
+};
+
+/// A small, seedable xorshift generator, so that a failing case can be
+/// reproduced from its seed alone.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // Xorshift gets stuck on 0, so mix the seed first.
+        Self(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A value in `0..n`. `n` must not be 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next_u64()%n
+    }
+
+    /// A value in `low..high`.
+    pub fn range(&mut self, low: i64, high: i64) -> i64 {
+        low + self.below((high - low) as u64) as i64
+    }
+}
+
+/// The ways of running a program that must all agree.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Runner {
+    /// Plain `step` calls on the given engine.
+    Plain(Engine),
+    /// `step_traced` calls on the reference engine, as the debugger does.
+    Traced,
+}
+
+pub const RUNNERS: [Runner; 3] = [
+    Runner::Plain(Engine::Reference),
+    Runner::Plain(Engine::Cached),
+    Runner::Traced,
+];
+
+/// Why a fuzzed run stopped.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Stop {
+    Halted,
+    NeedsInput,
+    Failed(IntcodeError),
+    OutOfFuel,
+}
+
+/// Everything observable about a fuzzed run.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Outcome {
+    pub stop: Stop,
+    pub output: Vec<i64>,
+    pub state: Checkpoint,
+}
+
+/// Two runners disagreeing about a program.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Mismatch {
+    pub program: Vec<i64>,
+    pub input: Vec<i64>,
+    pub runner: Runner,
+    pub expected: Outcome,
+    pub found: Outcome,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:?} disagrees with the reference engine", self.runner)?;
+        writeln!(f, "program: {}", crate::format_source(&self.program))?;
+        writeln!(f, "input: {:?}", self.input)?;
+        writeln!(f, "expected: {:?}, output {:?}", self.expected.stop,
+            self.expected.output)?;
+        writeln!(f, "found: {:?}, output {:?}", self.found.stop,
+            self.found.output)?;
+        for difference in self.expected.state.diff(&self.found.state) {
+            writeln!(f, "  {}", difference)?;
+        }
+        let (expected, found) = (&self.expected.state, &self.found.state);
+        if expected.memory.len() != found.memory.len() {
+            writeln!(f, "  memory size: {} -> {}", expected.memory.len(),
+                found.memory.len())?;
+        }
+        Ok(())
+    }
+}
+
+/// How much memory a fuzzed program may use. Random arithmetic easily
+/// produces huge addresses, and the default limit would let every such
+/// program allocate hundreds of megabytes.
+pub const MEMORY_LIMIT: usize = 1 << 16;
+
+/// Run `program` with `input` queued, executing at most `fuel` instructions.
+pub fn run(program: &[i64], input: &[i64], runner: Runner, fuel: u64)
+        -> Outcome {
+    let mut vm = Intcode::new(program.to_vec());
+    vm.set_memory_limit(MEMORY_LIMIT);
+    if let Runner::Plain(engine) = runner {
+        vm.set_engine(engine);
+    }
+    for &value in input {
+        vm.push_input(value);
+    }
+    let mut output = Vec::new();
+    let mut stop = Stop::OutOfFuel;
+    for _ in 0..fuel {
+        let state = match runner {
+            Runner::Plain(_) => vm.step(),
+            Runner::Traced => vm.step_traced().map(|(state, _)| state),
+        };
+        match state {
+            Ok(None) => continue,
+            Ok(Some(RunState::Output(value))) => {
+                output.push(value);
+                continue;
+            },
+            Ok(Some(RunState::Halted)) => stop = Stop::Halted,
+            Ok(Some(RunState::NeedsInput)) => stop = Stop::NeedsInput,
+            Ok(Some(RunState::BudgetExhausted)) => stop = Stop::OutOfFuel,
+            Err(e) => stop = Stop::Failed(e),
+        }
+        break;
+    }
+    Outcome { stop, output, state: vm.checkpoint() }
+}
+
+/// Run `program` with every runner, checking each against the reference
+/// engine. Returns the reference outcome when they all agree.
+pub fn check(program: &[i64], input: &[i64], fuel: u64)
+        -> Result<Outcome, Box<Mismatch>> {
+    let expected = run(program, input, RUNNERS[0], fuel);
+    for &runner in &RUNNERS[1..] {
+        let found = run(program, input, runner, fuel);
+        if expected != found {
+            return Err(Box::new(Mismatch {
+                program: program.to_vec(),
+                input: input.to_vec(),
+                runner,
+                expected,
+                found,
+            }));
+        }
+    }
+    Ok(expected)
+}
+
+/// Generate a random, well-formed program: a run of valid instructions
+/// followed by a Halt and some data. Operands mostly point into the program
+/// so that it reads, writes, and jumps into itself, and often rewrites its
+/// own code.
+pub fn random_program(rng: &mut Rng) -> Vec<i64> {
+    let instructions = rng.range(1, 24) as usize;
+    let data = rng.range(0, 8) as usize;
+    // Each instruction takes at most 4 words.
+    let size = 4*instructions + 1 + data;
+    let mut program = Vec::with_capacity(size);
+    for _ in 0..instructions {
+        let instruction = random_instruction(rng, size as i64);
+        program.extend(instruction.encode());
+    }
+    program.push(99);
+    for _ in 0..data {
+        program.push(rng.range(-10, 100));
+    }
+    program
+}
+
+/// A handful of small input values.
+pub fn random_input(rng: &mut Rng) -> Vec<i64> {
+    let len = rng.range(0, 6);
+    (0..len).map(|_| rng.range(-3, 10)).collect()
+}
+
+fn random_instruction(rng: &mut Rng, size: i64) -> Instruction {
+    let source = |rng: &mut Rng| match rng.below(3) {
+        0 => Operand::Address(rng.range(0, size + 4)),
+        1 => Operand::Immediate(rng.range(-5, size)),
+        _ => Operand::Relative(rng.range(-4, 8)),
+    };
+    let destination = |rng: &mut Rng| match rng.below(2) {
+        0 => Operand::Address(rng.range(0, size + 4)),
+        _ => Operand::Relative(rng.range(-4, 8)),
+    };
+    match rng.below(9) {
+        0 => Instruction::Add {
+            op1: source(rng),
+            op2: source(rng),
+            address: destination(rng),
+        },
+        1 => Instruction::Multiply {
+            op1: source(rng),
+            op2: source(rng),
+            address: destination(rng),
+        },
+        2 => Instruction::Input(destination(rng)),
+        3 => Instruction::Output(source(rng)),
+        4 => Instruction::JumpIfTrue {
+            condition: source(rng),
+            address: source(rng),
+        },
+        5 => Instruction::JumpIfFalse {
+            condition: source(rng),
+            address: source(rng),
+        },
+        6 => Instruction::TestLessThan {
+            op1: source(rng),
+            op2: source(rng),
+            address: destination(rng),
+        },
+        7 => Instruction::TestEqual {
+            op1: source(rng),
+            op2: source(rng),
+            address: destination(rng),
+        },
+        _ => Instruction::AdjustRelativeBase(source(rng)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runners_agree_on_random_programs() {
+        let mut rng = Rng::new(2019);
+        for _ in 0..500 {
+            let program = random_program(&mut rng);
+            let input = random_input(&mut rng);
+            if let Err(mismatch) = check(&program, &input, 1_000) {
+                panic!("{}", mismatch);
+            }
+        }
+    }
+
+    #[test]
+    fn failing_and_blocking_steps_agree() {
+        // Read two cells past the end, then fail writing to -4.
+        let outcome = check(&[20007, 10, 11, -4, 99], &[], 10).unwrap();
+        assert!(matches!(outcome.stop, Stop::Failed(_)));
+        assert_eq!(12, outcome.state.memory.len());
+        // Block reading into a cell past the end.
+        let outcome = check(&[3, 10, 99], &[], 10).unwrap();
+        assert_eq!(Stop::NeedsInput, outcome.stop);
+        assert_eq!(3, outcome.state.memory.len());
+    }
+
+    #[test]
+    fn fuel_stops_infinite_loops() {
+        // JT #1, #0 jumps to itself forever.
+        let outcome = check(&[1105, 1, 0], &[], 50).unwrap();
+        assert_eq!(Stop::OutOfFuel, outcome.stop);
+        assert_eq!(50, outcome.state.steps);
+    }
+}
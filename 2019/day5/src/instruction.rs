@@ -0,0 +1,371 @@
+use crate::error::IntcodeError;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Instruction {
+    Add {
+        op1:     Operand,
+        op2:     Operand,
+        address: Operand,
+    },
+    AdjustRelativeBase(Operand),
+    Halt,
+    Input(Operand),
+    JumpIfFalse {
+        condition: Operand,
+        address:   Operand,
+    },
+    JumpIfTrue {
+        condition: Operand,
+        address:   Operand,
+    },
+    Multiply {
+        op1:     Operand,
+        op2:     Operand,
+        address: Operand,
+    },
+    Output(Operand),
+    TestEqual {
+        op1: Operand,
+        op2: Operand,
+        address: Operand,
+    },
+    TestLessThan {
+        op1: Operand,
+        op2: Operand,
+        address: Operand,
+    },
+}
+
+impl Instruction {
+    /// Decode the instruction at `ip`.
+    pub fn decode(mem: &[i64], ip: usize)
+            -> Result<Instruction, IntcodeError> {
+        let word = mem.get(ip).copied().unwrap_or(0);
+        let opcode = OpCode::new(word, ip)?;
+        Ok(Instruction::new(mem, ip, opcode))
+    }
+
+    /// Words past the end of `mem` are read as 0, in keeping with the
+    /// machine's growable memory.
+    pub fn new(mem: &[i64], ip: usize, opcode: OpCode) -> Instruction {
+        let arg = |offset: usize| mem.get(ip + offset).copied().unwrap_or(0);
+        let OpCode {
+            opcode_type,
+            parameter1_mode,
+            parameter2_mode,
+            parameter3_mode,
+        } = opcode;
+        match opcode_type {
+            OpCodeType::Add => Instruction::Add {
+                op1:     Operand::new(parameter1_mode, arg(1)),
+                op2:     Operand::new(parameter2_mode, arg(2)),
+                address: Operand::new(parameter3_mode, arg(3)),
+            },
+            OpCodeType::AdjustRelativeBase => Instruction::AdjustRelativeBase(
+                Operand::new(parameter1_mode, arg(1)),
+            ),
+            OpCodeType::Halt => Instruction::Halt,
+            OpCodeType::JumpIfFalse => Instruction::JumpIfFalse {
+                condition: Operand::new(parameter1_mode, arg(1)),
+                address:   Operand::new(parameter2_mode, arg(2)),
+            },
+            OpCodeType::JumpIfTrue => Instruction::JumpIfTrue {
+                condition: Operand::new(parameter1_mode, arg(1)),
+                address:   Operand::new(parameter2_mode, arg(2)),
+            },
+            OpCodeType::Input => Instruction::Input(
+                Operand::new(parameter1_mode, arg(1)),
+            ),
+            OpCodeType::Multiply => Instruction::Multiply {
+                op1:     Operand::new(parameter1_mode, arg(1)),
+                op2:     Operand::new(parameter2_mode, arg(2)),
+                address: Operand::new(parameter3_mode, arg(3)),
+            },
+            OpCodeType::Output => Instruction::Output(
+                Operand::new(parameter1_mode, arg(1)),
+            ),
+            OpCodeType::TestLessThan => Instruction::TestLessThan {
+                op1:     Operand::new(parameter1_mode, arg(1)),
+                op2:     Operand::new(parameter2_mode, arg(2)),
+                address: Operand::new(parameter3_mode, arg(3)),
+            },
+            OpCodeType::TestEqual => Instruction::TestEqual {
+                op1:     Operand::new(parameter1_mode, arg(1)),
+                op2:     Operand::new(parameter2_mode, arg(2)),
+                address: Operand::new(parameter3_mode, arg(3)),
+            },
+        }
+    }
+
+    /// The number of words the instruction occupies, including the opcode.
+    pub fn size(&self) -> usize {
+        match self {
+            Instruction::Halt => 1,
+            Instruction::AdjustRelativeBase(_)
+            | Instruction::Input(_)
+            | Instruction::Output(_) => 2,
+            Instruction::JumpIfFalse { .. }
+            | Instruction::JumpIfTrue { .. } => 3,
+            Instruction::Add { .. }
+            | Instruction::Multiply { .. }
+            | Instruction::TestEqual { .. }
+            | Instruction::TestLessThan { .. } => 4,
+        }
+    }
+
+    pub fn mnemonic(&self) -> &'static str {
+        match self {
+            Instruction::Add { .. } => "ADD",
+            Instruction::AdjustRelativeBase(_) => "ARB",
+            Instruction::Halt => "HLT",
+            Instruction::Input(_) => "IN",
+            Instruction::JumpIfFalse { .. } => "JF",
+            Instruction::JumpIfTrue { .. } => "JT",
+            Instruction::Multiply { .. } => "MUL",
+            Instruction::Output(_) => "OUT",
+            Instruction::TestEqual { .. } => "EQ",
+            Instruction::TestLessThan { .. } => "LT",
+        }
+    }
+
+    /// The instruction's operands, in the order they appear in memory.
+    pub fn operands(&self) -> Vec<Operand> {
+        match *self {
+            Instruction::Halt => Vec::new(),
+            Instruction::AdjustRelativeBase(op1)
+            | Instruction::Input(op1)
+            | Instruction::Output(op1) => vec![op1],
+            Instruction::JumpIfFalse { condition, address }
+            | Instruction::JumpIfTrue { condition, address } => {
+                vec![condition, address]
+            },
+            Instruction::Add { op1, op2, address }
+            | Instruction::Multiply { op1, op2, address }
+            | Instruction::TestEqual { op1, op2, address }
+            | Instruction::TestLessThan { op1, op2, address } => {
+                vec![op1, op2, address]
+            },
+        }
+    }
+
+    /// Encode the instruction back into the words it was decoded from.
+    pub fn encode(&self) -> Vec<i64> {
+        let opcode = match self {
+            Instruction::Add { .. } => 1,
+            Instruction::Multiply { .. } => 2,
+            Instruction::Input(_) => 3,
+            Instruction::Output(_) => 4,
+            Instruction::JumpIfTrue { .. } => 5,
+            Instruction::JumpIfFalse { .. } => 6,
+            Instruction::TestLessThan { .. } => 7,
+            Instruction::TestEqual { .. } => 8,
+            Instruction::AdjustRelativeBase(_) => 9,
+            Instruction::Halt => 99,
+        };
+        let operands = self.operands();
+        let mut words = vec![opcode];
+        let mut scale = 100;
+        for op in operands {
+            words[0] += scale*op.mode_digit();
+            words.push(op.value());
+            scale *= 10;
+        }
+        words
+    }
+
+    /// The operand the instruction writes through, if any.
+    pub fn destination(&self) -> Option<Operand> {
+        match *self {
+            Instruction::Input(address)
+            | Instruction::Add { address, .. }
+            | Instruction::Multiply { address, .. }
+            | Instruction::TestEqual { address, .. }
+            | Instruction::TestLessThan { address, .. } => Some(address),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mode {
+    AddressMode,
+    ImmediateMode,
+    RelativeMode,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct OpCode {
+    pub opcode_type: OpCodeType,
+    pub parameter1_mode: Mode,
+    pub parameter2_mode: Mode,
+    pub parameter3_mode: Mode,
+}
+
+impl OpCode {
+    /// Decode an opcode word. `ip` is only used to give errors some context.
+    /// Only the modes of parameters the opcode actually has are decoded, so
+    /// any digits beyond them are ignored and those modes left as
+    /// `AddressMode`.
+    pub fn new(word: i64, ip: usize) -> Result<OpCode, IntcodeError> {
+        let opcode_type = match word%100 {
+             1 => OpCodeType::Add,
+             2 => OpCodeType::Multiply,
+             3 => OpCodeType::Input,
+             4 => OpCodeType::Output,
+             5 => OpCodeType::JumpIfTrue,
+             6 => OpCodeType::JumpIfFalse,
+             7 => OpCodeType::TestLessThan,
+             8 => OpCodeType::TestEqual,
+             9 => OpCodeType::AdjustRelativeBase,
+            99 => OpCodeType::Halt,
+            _ => return Err(IntcodeError::UnknownOpcode { ip, opcode: word }),
+        };
+        let count = opcode_type.parameter_count();
+        let mode = |parameter: u8| {
+            if count < usize::from(parameter) {
+                return Ok(Mode::AddressMode);
+            }
+            let digit = word/10_i64.pow(u32::from(parameter) + 1)%10;
+            match digit {
+                0 => Ok(Mode::AddressMode),
+                1 => Ok(Mode::ImmediateMode),
+                2 => Ok(Mode::RelativeMode),
+                _ => Err(IntcodeError::InvalidParameterMode {
+                    ip,
+                    opcode: word,
+                    parameter,
+                    mode: digit,
+                }),
+            }
+        };
+        Ok(Self {
+            opcode_type,
+            parameter1_mode: mode(1)?,
+            parameter2_mode: mode(2)?,
+            parameter3_mode: mode(3)?,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OpCodeType {
+    Add,
+    AdjustRelativeBase,
+    Halt,
+    Input,
+    JumpIfFalse,
+    JumpIfTrue,
+    Output,
+    Multiply,
+    TestEqual,
+    TestLessThan,
+}
+
+impl OpCodeType {
+    /// The number of parameters following the opcode word.
+    pub fn parameter_count(&self) -> usize {
+        match self {
+            OpCodeType::Halt => 0,
+            OpCodeType::AdjustRelativeBase
+            | OpCodeType::Input
+            | OpCodeType::Output => 1,
+            OpCodeType::JumpIfFalse | OpCodeType::JumpIfTrue => 2,
+            OpCodeType::Add
+            | OpCodeType::Multiply
+            | OpCodeType::TestEqual
+            | OpCodeType::TestLessThan => 3,
+        }
+    }
+}
+
+/// The opcodes and parameter modes a machine accepts, named after the puzzle
+/// that introduced them.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum InstructionSet {
+    /// Add, Multiply, and Halt, with positional parameters only.
+    Day2,
+    /// Adds input, output, jumps, and comparisons, and immediate parameters.
+    Day5,
+    /// Adds AdjustRelativeBase and relative parameters.
+    #[default]
+    Day9,
+}
+
+impl InstructionSet {
+    pub fn supports(&self, opcode_type: OpCodeType) -> bool {
+        match opcode_type {
+            OpCodeType::Add | OpCodeType::Multiply | OpCodeType::Halt => true,
+            OpCodeType::AdjustRelativeBase => InstructionSet::Day9 == *self,
+            _ => InstructionSet::Day2 != *self,
+        }
+    }
+
+    pub fn supports_mode(&self, mode: Mode) -> bool {
+        match mode {
+            Mode::AddressMode => true,
+            Mode::ImmediateMode => InstructionSet::Day2 != *self,
+            Mode::RelativeMode => InstructionSet::Day9 == *self,
+        }
+    }
+
+    /// Decode an opcode word, rejecting opcodes and modes outside the set.
+    /// Modes are only checked for the parameters the opcode actually has.
+    pub fn decode(&self, word: i64, ip: usize)
+            -> Result<OpCode, IntcodeError> {
+        let opcode = OpCode::new(word, ip)?;
+        if !self.supports(opcode.opcode_type) {
+            return Err(IntcodeError::UnknownOpcode { ip, opcode: word });
+        }
+        let modes = [
+            opcode.parameter1_mode,
+            opcode.parameter2_mode,
+            opcode.parameter3_mode,
+        ];
+        let count = opcode.opcode_type.parameter_count();
+        for (i, &mode) in modes.iter().enumerate().take(count) {
+            if !self.supports_mode(mode) {
+                let parameter = i as u8 + 1;
+                return Err(IntcodeError::InvalidParameterMode {
+                    ip,
+                    opcode: word,
+                    parameter,
+                    mode: word/10_i64.pow(u32::from(parameter) + 1)%10,
+                });
+            }
+        }
+        Ok(opcode)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Operand {
+    Address(i64),
+    Immediate(i64),
+    // An address relative to the machine's relative base.
+    Relative(i64),
+}
+
+impl Operand {
+    fn new(mode: Mode, argument: i64) -> Operand {
+        match mode {
+            Mode::AddressMode => Self::Address(argument),
+            Mode::ImmediateMode => Self::Immediate(argument),
+            Mode::RelativeMode => Self::Relative(argument),
+        }
+    }
+
+    /// The raw parameter word, without its mode.
+    pub fn value(&self) -> i64 {
+        match *self {
+            Self::Address(v) | Self::Immediate(v) | Self::Relative(v) => v,
+        }
+    }
+
+    fn mode_digit(&self) -> i64 {
+        match self {
+            Self::Address(_) => 0,
+            Self::Immediate(_) => 1,
+            Self::Relative(_) => 2,
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! An Intcode computer, as specified by the 2019 Advent of Code puzzles.
+
+pub mod amplifier;
+pub mod analysis;
+pub mod asm;
+pub mod checkpoint;
+pub mod debugger;
+pub mod device;
+pub mod disasm;
+mod error;
+pub mod fuzz;
+mod instruction;
+pub mod network;
+pub mod search;
+mod trace;
+mod vm;
+
+use std::{
+    fs,
+    io,
+    path::Path,
+    str::FromStr,
+};
+
+use aoc_common::{ParseError, parse::{self, Cursor}};
+
+pub use error::IntcodeError;
+pub use instruction::{
+    Instruction,
+    InstructionSet,
+    Mode,
+    OpCode,
+    OpCodeType,
+    Operand,
+};
+pub use trace::{MemoryWrite, TraceEvent};
+pub use vm::{DEFAULT_MEMORY_LIMIT, Engine, Intcode, RunState};
+
+/// Read a comma-separated Intcode program from a file.
+pub fn parse_source(path: &Path) -> io::Result<Vec<i64>> {
+    let src = fs::read_to_string(path)?;
+    parse_program(&src)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+// One word of a program's source.
+struct Word(i64);
+
+impl FromStr for Word {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cursor = Cursor::new(s);
+        let word = cursor.integer()?;
+        cursor.end()?;
+        Ok(Self(word))
+    }
+}
+
+/// Parse a comma-separated Intcode program. The program may be split over
+/// several lines, each ending in a comma or not; blank lines are skipped.
+pub fn parse_program(src: &str) -> Result<Vec<i64>, ParseError> {
+    let mut program = Vec::new();
+    for (index, line) in src.lines().enumerate() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            continue;
+        }
+        let words = parse::separated(line.strip_suffix(',').unwrap_or(line),
+            ',').map_err(|e| e.at_line(index + 1))?;
+        program.extend(words.into_iter().map(|Word(word)| word));
+    }
+    if program.is_empty() {
+        return Err(ParseError::new(1, "an Intcode program")
+            .found("the end of the input")
+            .at_line(1));
+    }
+    Ok(program)
+}
+
+/// Format a program image as comma-separated integers, the inverse of
+/// `parse_source`.
+pub fn format_source(program: &[i64]) -> String {
+    program
+        .iter()
+        .map(|word| word.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_programs() {
+        assert_eq!(Ok(vec![1, 0, 0, 0, 99]), parse_program("1,0,0,0,99\n"));
+        assert_eq!(Ok(vec![1, -2, 3, 99]),
+            parse_program(" 1, -2,\n\n3 ,99,\n"));
+        let error = |src| parse_program(src).unwrap_err().to_string();
+        assert_eq!("1:5: expected an integer, found the end of the line",
+            error("1,2,,99"));
+        assert_eq!("2:2: expected the end of the line, found 'x'",
+            error("1,2,\n3x,99"));
+        assert_eq!("1:1: expected an Intcode program, found the end of the \
+            input", error(""));
+        assert_eq!("1:3: expected a smaller integer, found \
+            99999999999999999999", error("1,99999999999999999999"));
+    }
+}
@@ -0,0 +1,237 @@
+use std::{
+    collections::VecDeque,
+    convert::TryFrom,
+};
+
+use crate::{
+    error::IntcodeError,
+    vm::{Intcode, RunState},
+};
+
+/// The address of the NAT, which watches for the network going idle.
+pub const NAT_ADDRESS: i64 = 255;
+
+/// A pair of values sent from one machine to another.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Packet {
+    pub source: usize,
+    pub destination: i64,
+    pub x: i64,
+    pub y: i64,
+}
+
+/// Something that happened to a packet during a `Network::tick`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Event {
+    /// The packet was queued for the machine it was addressed to.
+    Delivered(Packet),
+    /// The NAT took the packet, replacing the one it held.
+    Nat(Packet),
+    /// The network went idle, so the NAT sent its packet to machine 0. The
+    /// packet's source is the machine that originally sent it.
+    Wake(Packet),
+    /// The packet was addressed to a machine that doesn't exist.
+    Dropped(Packet),
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    vm: Intcode,
+    inbox: VecDeque<i64>,
+    outbox: Vec<i64>,
+    halted: bool,
+}
+
+/// A network of Intcode machines running cooperatively on one thread.
+///
+/// Machine `n` reads `n` as its first input. Every three values a machine
+/// outputs form a packet `(destination, x, y)`, which is queued for the
+/// destination to read as `x` then `y`. A machine reading from an empty queue
+/// reads -1. Packets sent to `NAT_ADDRESS` go to the NAT, which keeps only
+/// the last one; when the network goes idle, the NAT sends it to machine 0.
+///
+/// Machines take turns in address order. On its turn, a machine runs until
+/// it reads -1 a second time (having read -1 once and tried again means it's
+/// waiting) or halts, so a run is fully deterministic.
+#[derive(Clone, Debug)]
+pub struct Network {
+    nodes: Vec<Node>,
+    nat: Option<Packet>,
+}
+
+impl Network {
+    /// Boot `size` machines running `program`.
+    pub fn new(program: &[i64], size: usize) -> Self {
+        let nodes = (0..size)
+            .map(|address| Node {
+                vm: Intcode::new(program.to_vec()),
+                inbox: vec![address as i64].into(),
+                outbox: Vec::new(),
+                halted: false,
+            })
+            .collect();
+        Self { nodes, nat: None }
+    }
+
+    pub fn machine(&self, address: usize) -> &Intcode {
+        &self.nodes[address].vm
+    }
+
+    /// The packet the NAT is holding, if any.
+    pub fn nat(&self) -> Option<Packet> {
+        self.nat
+    }
+
+    /// Whether every machine has halted.
+    pub fn is_halted(&self) -> bool {
+        self.nodes.iter().all(|node| node.halted)
+    }
+
+    /// Give every machine one turn, then wake the network through the NAT if
+    /// no machine sent or received anything and machine 0 is still running.
+    /// Returns everything that happened to packets, in order.
+    pub fn tick(&mut self) -> Result<Vec<Event>, IntcodeError> {
+        let mut events = Vec::new();
+        let mut idle = true;
+        for source in 0..self.nodes.len() {
+            let (packets, active) = self.turn(source)?;
+            idle &= !active;
+            for packet in packets {
+                events.push(self.route(packet));
+            }
+        }
+        if idle && !self.nodes.is_empty() && !self.nodes[0].halted {
+            if let Some(packet) = self.nat {
+                self.nodes[0].inbox.extend(&[packet.x, packet.y]);
+                events.push(Event::Wake(packet));
+            }
+        }
+        Ok(events)
+    }
+
+    /// Tick until `done` accepts an event or `max_ticks` ticks have passed,
+    /// returning the accepted event.
+    pub fn run_until<F>(&mut self, max_ticks: usize, mut done: F)
+            -> Result<Option<Event>, IntcodeError>
+            where F: FnMut(&Event) -> bool {
+        for _ in 0..max_ticks {
+            if let Some(event) = self.tick()?.into_iter().find(&mut done) {
+                return Ok(Some(event));
+            }
+        }
+        Ok(None)
+    }
+
+    // Run one machine's turn, returning the packets it sent and whether it
+    // sent or received anything.
+    fn turn(&mut self, source: usize)
+            -> Result<(Vec<Packet>, bool), IntcodeError> {
+        let node = &mut self.nodes[source];
+        let mut packets = Vec::new();
+        let mut active = false;
+        let mut starved = false;
+        while !node.halted {
+            match node.vm.run()? {
+                RunState::Halted => node.halted = true,
+                RunState::BudgetExhausted => {
+                    return Err(node.vm.budget_exhausted());
+                },
+                RunState::NeedsInput => match node.inbox.pop_front() {
+                    Some(value) => {
+                        node.vm.push_input(value);
+                        active = true;
+                    },
+                    None if starved => break,
+                    None => {
+                        node.vm.push_input(-1);
+                        starved = true;
+                    },
+                },
+                RunState::Output(value) => {
+                    node.outbox.push(value);
+                    active = true;
+                    if let [destination, x, y] = node.outbox[..] {
+                        packets.push(Packet { source, destination, x, y });
+                        node.outbox.clear();
+                    }
+                },
+            }
+        }
+        Ok((packets, active))
+    }
+
+    fn route(&mut self, packet: Packet) -> Event {
+        if NAT_ADDRESS == packet.destination {
+            self.nat = Some(packet);
+            return Event::Nat(packet);
+        }
+        let node = usize::try_from(packet.destination)
+            .ok()
+            .and_then(|address| self.nodes.get_mut(address));
+        match node {
+            Some(node) => {
+                node.inbox.extend(&[packet.x, packet.y]);
+                Event::Delivered(packet)
+            },
+            None => Event::Dropped(packet),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asm;
+
+    fn ring() -> Network {
+        let program = asm::assemble(include_str!("../asm/ring.asm")).unwrap();
+        Network::new(&program, 4)
+    }
+
+    #[test]
+    fn packets_travel_the_ring() {
+        let mut network = ring();
+        let events = network.tick().unwrap();
+        let hops: Vec<(usize, i64, i64)> = events
+            .iter()
+            .map(|event| match *event {
+                Event::Delivered(p) | Event::Nat(p) => {
+                    (p.source, p.destination, p.y)
+                },
+                _ => panic!("unexpected {:?}", event),
+            })
+            .collect();
+        assert_eq!(vec![(0, 1, 1), (1, 2, 2), (2, 3, 3), (3, 255, 4)], hops);
+        assert_eq!(Some(4), network.nat().map(|p| p.y));
+    }
+
+    #[test]
+    fn nat_wakes_an_idle_network() {
+        let mut network = ring();
+        let mut wakes = Vec::new();
+        for _ in 0..3 {
+            let event = network.run_until(10, |event| {
+                matches!(event, Event::Wake(_))
+            });
+            match event {
+                Ok(Some(Event::Wake(packet))) => wakes.push(packet.y),
+                other => panic!("unexpected {:?}", other),
+            }
+        }
+        assert_eq!(vec![4, 8, 12], wakes);
+        assert!(!network.is_halted());
+    }
+
+    #[test]
+    fn halted_machines_drop_out() {
+        // Send (1, 7, 8), (9, 0, 0), and (255, 5, 6), then halt.
+        let program = [104, 1, 104, 7, 104, 8, 104, 9, 104, 0, 104, 0, 104,
+            255, 104, 5, 104, 6, 99];
+        let mut network = Network::new(&program, 2);
+        let events = network.tick().unwrap();
+        assert_eq!(6, events.len());
+        assert!(matches!(events[1], Event::Dropped(Packet { source: 0, .. })));
+        assert!(network.is_halted());
+        assert_eq!(Ok(Vec::new()), network.tick());
+    }
+}
@@ -0,0 +1,203 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, self},
+    ops::Range,
+    thread,
+};
+
+use crate::vm::Intcode;
+
+/// Why a search couldn't start.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SearchError {
+    /// There are more combinations than fit in a `u64`.
+    TooManyCombinations,
+    /// A patch address is at or beyond the machine's memory limit.
+    AddressOutOfBounds { address: usize, limit: usize },
+}
+
+impl Display for SearchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManyCombinations => write!(f,
+                "too many combinations to search"),
+            Self::AddressOutOfBounds { address, limit } => write!(f,
+                "cannot patch address {}: memory is limited to {} words",
+                address, limit),
+        }
+    }
+}
+
+impl Error for SearchError {}
+
+/// The step limit `find_noun_and_verb` gives each candidate unless the
+/// machine already has one. Day 2 programs run straight through in a few
+/// dozen steps, so anything running this long is stuck.
+pub const SEARCH_STEP_LIMIT: u64 = 100_000;
+
+/// Patch every combination of values into a program and keep the ones that
+/// pass a test.
+///
+/// `machine` is the starting point: its memory is the program, and its
+/// settings (memory limit, instruction set, step limit, and loop detection)
+/// carry over to every run. Each address in `addresses` is paired with the
+/// range of values at the same index of `ranges`. For every combination, a
+/// copy of the machine is reset to the program, the values are written to
+/// their addresses (growing memory for any past the end of the program),
+/// and the copy runs to completion with no input. The search fails before
+/// running anything if an address is beyond the machine's memory limit, or
+/// if the number of combinations doesn't fit in a `u64`.
+/// Combinations whose run fails or exhausts the step limit are skipped, so
+/// give `machine` a step limit if a patch could make the program loop
+/// forever. The rest are kept if `test` accepts the halted machine. The
+/// combinations are spread over one thread per available CPU, and every
+/// match is returned in the order the combinations were enumerated (last
+/// address varying fastest).
+pub fn search<F>(machine: &Intcode, addresses: &[usize], ranges: &[Range<i64>],
+        test: F) -> Result<Vec<Vec<i64>>, SearchError>
+        where F: Fn(&Intcode) -> bool + Sync {
+    assert_eq!(addresses.len(), ranges.len());
+    let limit = machine.memory_limit();
+    if let Some(&address) = addresses.iter().find(|&&a| limit <= a) {
+        return Err(SearchError::AddressOutOfBounds { address, limit });
+    }
+    let sizes: Vec<u64> = ranges
+        .iter()
+        .map(|range| {
+            let size = range.end.checked_sub(range.start)
+                .ok_or(SearchError::TooManyCombinations)?;
+            Ok(size.max(0) as u64)
+        })
+        .collect::<Result<_, _>>()?;
+    let total = sizes
+        .iter()
+        .try_fold(1u64, |total, &size| total.checked_mul(size))
+        .ok_or(SearchError::TooManyCombinations)?;
+    let threads = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(total.max(1) as usize) as u64;
+    let program = machine.memory();
+    let test = &test;
+    let sizes = &sizes;
+    let mut matches: Vec<(u64, Vec<i64>)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|first| {
+                let mut vm = machine.clone();
+                scope.spawn(move || {
+                    let mut found = Vec::new();
+                    // Interleave candidates so each thread gets a similar mix.
+                    for index in (first..total).step_by(threads as usize) {
+                        let values = candidate(index, sizes, ranges);
+                        vm.reset(program);
+                        // Every address is within the limit, so the patches
+                        // can't fail.
+                        let patched = addresses
+                            .iter()
+                            .zip(&values)
+                            .all(|(&address, &value)| {
+                                vm.poke(address, value).is_ok()
+                            });
+                        if patched && vm.run_to_halt().is_ok() && test(&vm) {
+                            found.push((index, values));
+                        }
+                    }
+                    found
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect()
+    });
+    matches.sort_unstable_by_key(|&(index, _)| index);
+    Ok(matches.into_iter().map(|(_, values)| values).collect())
+}
+
+/// Find every noun (address 1) and verb (address 2) in `range` for which the
+/// machine's program leaves `target` in address 0. Candidates that fail or
+/// run for more than `SEARCH_STEP_LIMIT` steps are skipped.
+pub fn find_noun_and_verb(machine: &Intcode, target: i64, range: Range<i64>)
+        -> Result<Vec<(i64, i64)>, SearchError> {
+    let mut machine = machine.clone();
+    if machine.step_limit().is_none() {
+        machine.set_step_limit(Some(SEARCH_STEP_LIMIT));
+    }
+    Ok(search(&machine, &[1, 2], &[range.clone(), range],
+            |vm| target == vm.memory()[0])?
+        .into_iter()
+        .map(|values| (values[0], values[1]))
+        .collect())
+}
+
+// Decode a combination's index as a mixed-radix number, one digit per range.
+fn candidate(mut index: u64, sizes: &[u64], ranges: &[Range<i64>])
+        -> Vec<i64> {
+    let mut values = vec![0; ranges.len()];
+    for i in (0..ranges.len()).rev() {
+        values[i] = ranges[i].start + (index%sizes[i]) as i64;
+        index /= sizes[i];
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_every_match_in_order() {
+        // mem[0] = noun + verb, both immediate, then halt.
+        let vm = Intcode::new(vec![1101, 0, 0, 0, 99]);
+        let matches = find_noun_and_verb(&vm, 5, 0..10).unwrap();
+        let expected: Vec<(i64, i64)> = (0..=5).map(|n| (n, 5 - n)).collect();
+        assert_eq!(expected, matches);
+    }
+
+    #[test]
+    fn skips_failing_candidates() {
+        // Jump to the address in mem[2]. Landing on mem[3] always halts,
+        // landing on mem[4] only halts if it holds 99, and mem[5] is past the
+        // end of the program, which reads as the invalid opcode 0.
+        let vm = Intcode::new(vec![1105, 1, 0, 99, 0]);
+        let matches = search(&vm, &[2, 4], &[3..6, 98..100], |_| true);
+        assert_eq!(Ok(vec![vec![3, 98], vec![3, 99], vec![4, 99]]), matches);
+    }
+
+    #[test]
+    fn patches_past_the_end_of_the_program() {
+        // mem[0] = mem[5] + mem[6], then halt. Both addresses are past the
+        // end of the program until they're patched.
+        let vm = Intcode::new(vec![1, 5, 6, 0, 99]);
+        let matches = search(&vm, &[5, 6], &[0..4, 0..4],
+            |vm| 3 == vm.memory()[0]);
+        assert_eq!(Ok(vec![vec![0, 3], vec![1, 2], vec![2, 1], vec![3, 0]]),
+            matches);
+    }
+
+    #[test]
+    fn skips_endless_candidates() {
+        // If the noun is nonzero, jump to the verb. A verb of 0 jumps back to
+        // the jump forever, and a verb of 1 runs into invalid code.
+        let vm = Intcode::new(vec![1105, 0, 0, 99]);
+        assert_eq!(Ok(vec![(0, 0), (0, 1)]),
+            find_noun_and_verb(&vm, 1105, 0..2));
+    }
+
+    #[test]
+    fn rejects_searches_that_cannot_run() {
+        let mut vm = Intcode::new(vec![99]);
+        assert_eq!(Err(SearchError::TooManyCombinations),
+            search(&vm, &[1, 2], &[i64::MIN..i64::MAX, 0..1], |_| true));
+        let wide = -(1 << 40)..1 << 40;
+        assert_eq!(Err(SearchError::TooManyCombinations),
+            search(&vm, &[1, 2], &[wide.clone(), wide], |_| true));
+        vm.set_memory_limit(10);
+        assert_eq!(Err(SearchError::AddressOutOfBounds {
+            address: 10,
+            limit: 10,
+        }), search(&vm, &[1, 10], &[0..2, 0..2], |_| true));
+        assert_eq!(Ok(vec![vec![0, 0]]),
+            search(&vm, &[8, 9], &[0..1, 0..1], |_| true));
+    }
+}
@@ -0,0 +1,78 @@
+use std::fmt::{self, Debug, Display, Formatter};
+
+use crate::instruction::Instruction;
+
+/// A single memory cell changing value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MemoryWrite {
+    pub address: usize,
+    pub old: i64,
+    pub new: i64,
+}
+
+/// Everything that happened during one executed instruction.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TraceEvent {
+    /// The instruction pointer and relative base before the instruction ran.
+    pub ip: usize,
+    pub relative_base: i64,
+    pub instruction: Instruction,
+    /// One value per operand: the value read for source operands, and the
+    /// resolved address for the destination operand.
+    pub values: Vec<i64>,
+    /// The cell the instruction wrote to, if any. An Input instruction that
+    /// blocked for lack of input writes nothing.
+    pub write: Option<MemoryWrite>,
+}
+
+impl Display for TraceEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:>6}: {:<32}", self.ip, self.instruction.to_string())?;
+        let values = self.values
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, " ({})", values)?;
+        if let Some(MemoryWrite { address, old, new }) = self.write {
+            write!(f, " mem[{}]: {} -> {}", address, old, new)?;
+        }
+        Ok(())
+    }
+}
+
+type Hook = Box<dyn FnMut(&TraceEvent) + Send>;
+
+/// An optional callback run after every instruction a machine executes.
+/// Cloning a machine doesn't clone its tracer; the clone runs untraced.
+#[derive(Default)]
+pub(crate) struct Tracer(Option<Hook>);
+
+impl Tracer {
+    pub(crate) fn new(hook: Hook) -> Self {
+        Self(Some(hook))
+    }
+
+    pub(crate) fn is_active(&self) -> bool {
+        self.0.is_some()
+    }
+
+    pub(crate) fn trace(&mut self, event: &TraceEvent) {
+        if let Some(hook) = self.0.as_mut() {
+            hook(event);
+        }
+    }
+}
+
+impl Clone for Tracer {
+    fn clone(&self) -> Self {
+        Self(None)
+    }
+}
+
+impl Debug for Tracer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let state = if self.is_active() { "active" } else { "none" };
+        write!(f, "Tracer({})", state)
+    }
+}
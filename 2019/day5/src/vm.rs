@@ -0,0 +1,998 @@
+use std::{
+    collections::VecDeque,
+    convert::TryFrom,
+    io,
+};
+
+use crate::{
+    checkpoint::Checkpoint,
+    device::{InputDevice, OutputDevice, UnparsableInput},
+    error::IntcodeError,
+    instruction::{Instruction, InstructionSet, Operand},
+    trace::{MemoryWrite, TraceEvent, Tracer},
+};
+
+/// The default number of words a machine's memory may grow to.
+pub const DEFAULT_MEMORY_LIMIT: usize = 1 << 24;
+
+const MAX_INSTRUCTION_SIZE: usize = 4;
+
+/// How a machine fetches instructions. Both engines produce exactly the same
+/// results.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Engine {
+    /// Decode the instruction at `ip` from scratch on every step.
+    #[default]
+    Reference,
+    /// Keep decoded instructions by address, forgetting an instruction
+    /// whenever one of its words is written.
+    Cached,
+}
+
+/// The reason an `Intcode` machine stopped running.
+#[derive(Debug, Eq, PartialEq)]
+pub enum RunState {
+    /// The machine executed a Halt instruction.
+    Halted,
+    /// The machine reached an Input instruction while its input queue was
+    /// empty. Push some input and call `run` again to continue.
+    NeedsInput,
+    /// The machine executed an Output instruction.
+    Output(i64),
+    /// The machine has executed as many instructions as its step limit
+    /// allows. Raise or remove the limit and call `run` again to continue.
+    BudgetExhausted,
+}
+
+// What loop detection remembers: one earlier state, which every later state
+// is compared against. Once `power` steps have passed since it was saved,
+// it's replaced by the current state and `power` doubles (Brent's cycle
+// detection), so however long a loop is, the saved state eventually lands
+// in it and stays there for long enough to see it come round again.
+#[derive(Clone, Debug, Default)]
+struct LoopDetector {
+    saved: Option<SavedState>,
+    power: u64,
+}
+
+#[derive(Clone, Debug, Default)]
+struct SavedState {
+    ip: usize,
+    relative_base: i64,
+    mem: Vec<i64>,
+    input: VecDeque<i64>,
+    steps: u64,
+}
+
+/// An Intcode computer with its own memory and input/output queues. Execution
+/// pauses whenever the machine halts, blocks on input, or produces output, so
+/// several machines can be driven from the same thread.
+#[derive(Clone, Debug)]
+pub struct Intcode {
+    mem: Vec<i64>,
+    ip: usize,
+    relative_base: i64,
+    memory_limit: usize,
+    instruction_set: InstructionSet,
+    engine: Engine,
+    cache: Vec<Option<Instruction>>,
+    steps: u64,
+    step_limit: Option<u64>,
+    // The state loop detection compares against, when it's on.
+    loops: Option<LoopDetector>,
+    input: VecDeque<i64>,
+    output: VecDeque<i64>,
+    tracer: Tracer,
+}
+
+impl Intcode {
+    pub fn new(program: Vec<i64>) -> Self {
+        Self {
+            mem: program,
+            ip: 0,
+            relative_base: 0,
+            memory_limit: DEFAULT_MEMORY_LIMIT,
+            instruction_set: InstructionSet::default(),
+            engine: Engine::default(),
+            cache: Vec::new(),
+            steps: 0,
+            step_limit: None,
+            loops: None,
+            input: VecDeque::new(),
+            output: VecDeque::new(),
+            tracer: Tracer::default(),
+        }
+    }
+
+    /// Return the machine to a pristine state running `image`, reusing the
+    /// memory already allocated. The memory limit, instruction set, engine,
+    /// step limit, loop detection, and tracer are kept.
+    pub fn reset(&mut self, image: &[i64]) {
+        if self.cache.is_empty() {
+            self.mem.clear();
+            self.mem.extend_from_slice(image);
+        } else {
+            // Keep every cached instruction whose words are unchanged, so
+            // rerunning a lightly patched program stays fast. Instructions
+            // running past the end of the image may have read grown memory.
+            self.mem.truncate(image.len());
+            self.cache.truncate(image.len());
+            let tail = image.len().saturating_sub(MAX_INSTRUCTION_SIZE - 1);
+            for entry in self.cache.iter_mut().skip(tail) {
+                *entry = None;
+            }
+            for (address, &word) in image.iter().enumerate() {
+                if self.mem.get(address) != Some(&word) {
+                    self.store(address, word);
+                }
+            }
+        }
+        self.ip = 0;
+        self.relative_base = 0;
+        self.steps = 0;
+        self.input.clear();
+        self.output.clear();
+        self.forget_states();
+    }
+
+    /// Capture everything needed to resume the machine later.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            memory: self.mem.clone(),
+            ip: self.ip,
+            relative_base: self.relative_base,
+            steps: self.steps,
+            memory_limit: self.memory_limit,
+            instruction_set: self.instruction_set,
+            input: self.input.iter().copied().collect(),
+            output: self.output.iter().copied().collect(),
+        }
+    }
+
+    /// Build a machine that resumes from a checkpoint.
+    pub fn from_checkpoint(checkpoint: &Checkpoint) -> Self {
+        let mut vm = Self::new(Vec::new());
+        vm.restore(checkpoint);
+        vm
+    }
+
+    /// Put the machine back into a checkpointed state. The engine, step
+    /// limit, loop detection, and tracer are kept.
+    pub fn restore(&mut self, checkpoint: &Checkpoint) {
+        self.cache.clear();
+        self.forget_states();
+        self.mem.clone_from(&checkpoint.memory);
+        self.ip = checkpoint.ip;
+        self.relative_base = checkpoint.relative_base;
+        self.steps = checkpoint.steps;
+        self.memory_limit = checkpoint.memory_limit;
+        self.instruction_set = checkpoint.instruction_set;
+        self.input = checkpoint.input.iter().copied().collect();
+        self.output = checkpoint.output.iter().copied().collect();
+    }
+
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    pub fn relative_base(&self) -> i64 {
+        self.relative_base
+    }
+
+    /// The number of instructions executed so far. Halt and Input
+    /// instructions that block don't count.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// The machine's memory. Memory grows as the program touches addresses
+    /// past its end, so this may be longer than the program it was given.
+    pub fn memory(&self) -> &[i64] {
+        &self.mem
+    }
+
+    /// Writes made through the returned slice can't be tracked, so this
+    /// empties the instruction cache.
+    pub fn memory_mut(&mut self) -> &mut [i64] {
+        self.cache.clear();
+        self.forget_states();
+        &mut self.mem
+    }
+
+    /// Write one word of memory, growing memory as needed. Unlike writing
+    /// through `memory_mut`, this only forgets the cached instructions the
+    /// write touches. Like an instruction's write, it fails with
+    /// `IntcodeError::AddressOutOfBounds` at or beyond the memory limit.
+    pub fn poke(&mut self, address: usize, value: i64)
+            -> Result<(), IntcodeError> {
+        if self.memory_limit <= address {
+            return Err(IntcodeError::AddressOutOfBounds {
+                ip: self.ip,
+                opcode: self.opcode(),
+                address: i64::try_from(address).unwrap_or(i64::MAX),
+            });
+        }
+        self.store(address, value);
+        self.forget_states();
+        Ok(())
+    }
+
+    // Write one word on behalf of an instruction.
+    fn store(&mut self, address: usize, value: i64) {
+        *self.cell(address) = value;
+        if self.cache.is_empty() {
+            return;
+        }
+        // Forget any cached instruction with a word at `address`.
+        for offset in 0..MAX_INSTRUCTION_SIZE.min(address + 1) {
+            let start = address - offset;
+            if let Some(entry) = self.cache.get_mut(start) {
+                if entry.is_some_and(|i| offset < i.size()) {
+                    *entry = None;
+                }
+            }
+        }
+    }
+
+    pub fn memory_limit(&self) -> usize {
+        self.memory_limit
+    }
+
+    /// Set the number of words memory may grow to. Accessing an address at
+    /// or beyond the limit fails with `IntcodeError::AddressOutOfBounds`.
+    pub fn set_memory_limit(&mut self, limit: usize) {
+        self.memory_limit = limit;
+    }
+
+    pub fn step_limit(&self) -> Option<u64> {
+        self.step_limit
+    }
+
+    /// Stop running with `RunState::BudgetExhausted` once `steps` reaches
+    /// `limit`, or never stop if `limit` is `None`. The limit counts every
+    /// instruction since the program was loaded, so raising it lets a
+    /// stopped machine carry on.
+    pub fn set_step_limit(&mut self, limit: Option<u64>) {
+        self.step_limit = limit;
+    }
+
+    pub fn loop_detection(&self) -> bool {
+        self.loops.is_some()
+    }
+
+    /// Turn loop detection on or off. While it's on, the machine compares
+    /// its instruction pointer, relative base, memory, and input queue after
+    /// every step with a copy saved earlier, and fails with
+    /// `IntcodeError::InfiniteLoop` if they're all equal: a machine in the
+    /// same state always does the same thing, so it would go round the same
+    /// cycle forever. Only exact matches count, so a machine that would halt
+    /// is never stopped.
+    ///
+    /// The copy is retaken after 1, 2, 4, 8, ... steps, so detection keeps
+    /// a single copy of memory, however long the machine runs, and a loop
+    /// is caught within about twice the steps taken to reach it or twice
+    /// its length, whichever is more. Memory is only compared when the
+    /// registers match, but that, and retaking the copy, costs time
+    /// proportional to the size of memory. This only catches loops that
+    /// leave memory as they found it; a step limit catches the rest.
+    /// Anything done to the machine from outside, such as pushing input,
+    /// starts detection afresh.
+    pub fn set_loop_detection(&mut self, enabled: bool) {
+        self.loops = if enabled {
+            Some(LoopDetector::default())
+        } else {
+            None
+        };
+    }
+
+    pub fn instruction_set(&self) -> InstructionSet {
+        self.instruction_set
+    }
+
+    /// Restrict the machine to `instruction_set`. Fetching an opcode or
+    /// parameter mode outside the set fails as if it didn't exist.
+    pub fn set_instruction_set(&mut self, instruction_set: InstructionSet) {
+        self.instruction_set = instruction_set;
+        self.cache.clear();
+    }
+
+    pub fn engine(&self) -> Engine {
+        self.engine
+    }
+
+    /// Switch engines. This can be done at any point, even mid-run.
+    pub fn set_engine(&mut self, engine: Engine) {
+        self.engine = engine;
+        self.cache.clear();
+    }
+
+    /// Call `hook` after every instruction the machine executes from now on.
+    pub fn set_tracer<F>(&mut self, hook: F)
+            where F: FnMut(&TraceEvent) + Send + 'static {
+        self.tracer = Tracer::new(Box::new(hook));
+    }
+
+    pub fn clear_tracer(&mut self) {
+        self.tracer = Tracer::default();
+    }
+
+    /// Queue a value to be consumed by a future Input instruction.
+    pub fn push_input(&mut self, value: i64) {
+        self.input.push_back(value);
+        self.forget_states();
+    }
+
+    /// Parse a line of text as an integer and queue it as input.
+    pub fn push_input_str(&mut self, s: &str) -> Result<(), IntcodeError> {
+        match s.trim().parse() {
+            Ok(value) => {
+                self.push_input(value);
+                Ok(())
+            },
+            Err(_) => Err(IntcodeError::UnparsableInput {
+                ip: self.ip,
+                opcode: self.opcode(),
+                input: s.to_string(),
+            }),
+        }
+    }
+
+    /// Put a value back at the front of the input queue.
+    pub(crate) fn unread_input(&mut self, value: i64) {
+        self.input.push_front(value);
+        self.forget_states();
+    }
+
+    /// Undo one step's effect on the registers, moving the machine back to
+    /// the instruction pointer and relative base recorded in a `TraceEvent`.
+    pub(crate) fn restore_registers(&mut self, ip: usize, relative_base: i64) {
+        self.ip = ip;
+        self.relative_base = relative_base;
+        self.steps = self.steps.saturating_sub(1);
+        self.forget_states();
+    }
+
+    /// Take the oldest value collected by `run_until_blocked`.
+    pub fn pop_output(&mut self) -> Option<i64> {
+        self.output.pop_front()
+    }
+
+    /// Take every value collected by `run_until_blocked`.
+    pub fn drain_output(&mut self) -> Vec<i64> {
+        self.output.drain(..).collect()
+    }
+
+    /// Run until the machine halts, needs input, or produces a value.
+    pub fn run(&mut self) -> Result<RunState, IntcodeError> {
+        loop {
+            if let Some(state) = self.step()? {
+                return Ok(state);
+            }
+        }
+    }
+
+    /// Run until the machine halts or needs input. Values produced along the
+    /// way are appended to the output queue.
+    pub fn run_until_blocked(&mut self) -> Result<RunState, IntcodeError> {
+        loop {
+            match self.run()? {
+                RunState::Output(value) => self.output.push_back(value),
+                state => return Ok(state),
+            }
+        }
+    }
+
+    /// Run to completion using only the input queued so far, returning every
+    /// value the machine produced.
+    pub fn run_to_halt(&mut self) -> Result<Vec<i64>, IntcodeError> {
+        match self.run_until_blocked()? {
+            RunState::NeedsInput => Err(self.input_exhausted()),
+            RunState::BudgetExhausted => Err(self.budget_exhausted()),
+            _ => Ok(self.drain_output()),
+        }
+    }
+
+    /// Run until the machine halts, exhausts its step limit, or `input` has
+    /// nothing more to give it, feeding Input instructions from `input` once
+    /// the input queue is empty and sending every value produced to
+    /// `output`.
+    pub fn run_with<I, O>(&mut self, input: &mut I, output: &mut O)
+            -> Result<RunState, IntcodeError>
+            where I: InputDevice + ?Sized, O: OutputDevice + ?Sized {
+        loop {
+            let ip = self.ip;
+            match self.step()? {
+                None => (),
+                Some(RunState::Output(value)) => {
+                    if let Err(e) = output.write(value) {
+                        return Err(self.device_error(ip, e));
+                    }
+                },
+                Some(RunState::NeedsInput) => match input.read() {
+                    Ok(Some(value)) => self.push_input(value),
+                    Ok(None) => return Ok(RunState::NeedsInput),
+                    Err(e) => return Err(self.device_error(ip, e)),
+                },
+                Some(state) => return Ok(state),
+            }
+        }
+    }
+
+    /// The error describing this machine being starved of input, for callers
+    /// that have no more input to give it.
+    pub fn input_exhausted(&self) -> IntcodeError {
+        IntcodeError::InputExhausted { ip: self.ip, opcode: self.opcode() }
+    }
+
+    /// The error describing this machine running out of steps, for callers
+    /// that can't give it any more.
+    pub fn budget_exhausted(&self) -> IntcodeError {
+        IntcodeError::BudgetExhausted {
+            ip: self.ip,
+            opcode: self.opcode(),
+            limit: self.step_limit.unwrap_or(self.steps),
+        }
+    }
+
+    /// Execute a single instruction. Returns the state that execution paused
+    /// in, if any. Halt and blocking Input instructions leave `ip` unchanged,
+    /// so stepping a halted or starved machine is idempotent. A failing
+    /// instruction also leaves `ip` unchanged. Once the step limit is
+    /// reached, nothing is executed and the state is always
+    /// `RunState::BudgetExhausted`.
+    pub fn step(&mut self) -> Result<Option<RunState>, IntcodeError> {
+        if self.step_limit.is_some_and(|limit| limit <= self.steps) {
+            return Ok(Some(RunState::BudgetExhausted));
+        }
+        let steps = self.steps;
+        let state = if self.tracer.is_active() {
+            let (state, event) = self.step_traced()?;
+            self.tracer.trace(&event);
+            state
+        } else {
+            let instruction = self.fetch()?;
+            self.execute(instruction)?
+        };
+        if steps != self.steps && self.loops.is_some() {
+            self.check_for_loop()?;
+        }
+        Ok(state)
+    }
+
+    /// Execute a single instruction like `step`, also describing what it did.
+    /// This ignores the step limit and loop detection, so a debugger can
+    /// always take one more step.
+    pub fn step_traced(&mut self)
+            -> Result<(Option<RunState>, TraceEvent), IntcodeError> {
+        let ip = self.ip;
+        let relative_base = self.relative_base;
+        let instruction = self.fetch()?;
+        // The destination, when there is one, is always the last operand.
+        // Sources are read first, as `execute` does, so a failing
+        // instruction leaves memory exactly as an untraced step would.
+        let mut values = Vec::new();
+        let operands = instruction.operands();
+        let destination_op = instruction.destination();
+        let sources = operands.len() - destination_op.map_or(0, |_| 1);
+        for &op in &operands[..sources] {
+            values.push(self.read(op)?);
+        }
+        let destination = match destination_op {
+            Some(op) => Some(self.address(op)?),
+            None => None,
+        };
+        if let Some(address) = destination {
+            values.push(address as i64);
+        }
+        // Peek rather than grow memory, in case the instruction blocks.
+        let old = destination
+            .map(|address| self.mem.get(address).copied().unwrap_or(0));
+        let state = self.execute(instruction)?;
+        let write = match (destination, old, &state) {
+            (_, _, Some(RunState::NeedsInput)) => None,
+            (Some(address), Some(old), _) => Some(MemoryWrite {
+                address,
+                old,
+                new: self.mem[address],
+            }),
+            _ => None,
+        };
+        let event = TraceEvent {
+            ip,
+            relative_base,
+            instruction,
+            values,
+            write,
+        };
+        Ok((state, event))
+    }
+
+    fn fetch(&mut self) -> Result<Instruction, IntcodeError> {
+        if self.memory_limit <= self.ip {
+            return Err(IntcodeError::AddressOutOfBounds {
+                ip: self.ip,
+                opcode: self.opcode(),
+                address: self.ip as i64,
+            });
+        }
+        if let Some(&Some(instruction)) = self.cache.get(self.ip) {
+            return Ok(instruction);
+        }
+        let word = *self.cell(self.ip);
+        let opcode = self.instruction_set.decode(word, self.ip)?;
+        let instruction = Instruction::new(&self.mem, self.ip, opcode);
+        if Engine::Cached == self.engine {
+            if self.cache.len() <= self.ip {
+                self.cache.resize(self.mem.len(), None);
+            }
+            self.cache[self.ip] = Some(instruction);
+        }
+        Ok(instruction)
+    }
+
+    fn execute(&mut self, instruction: Instruction)
+            -> Result<Option<RunState>, IntcodeError> {
+        match instruction {
+            Instruction::Add { op1, op2, address } => {
+                let op1 = self.read(op1)?;
+                let op2 = self.read(op2)?;
+                let address = self.address(address)?;
+                self.store(address, op1.wrapping_add(op2));
+                self.ip += 4;
+            },
+            Instruction::AdjustRelativeBase(op1) => {
+                let offset = self.read(op1)?;
+                self.relative_base = self.relative_base.wrapping_add(offset);
+                self.ip += 2;
+            },
+            Instruction::Halt => return Ok(Some(RunState::Halted)),
+            Instruction::Input(op1) => {
+                let address = self.address(op1)?;
+                match self.input.pop_front() {
+                    Some(value) => self.store(address, value),
+                    None => return Ok(Some(RunState::NeedsInput)),
+                }
+                self.ip += 2;
+            },
+            Instruction::JumpIfFalse { condition, address } => {
+                let condition = self.read(condition)?;
+                let address = self.read(address)?;
+                if 0 == condition {
+                    self.ip = self.location(address)?;
+                } else {
+                    self.ip += 3;
+                }
+            },
+            Instruction::JumpIfTrue { condition, address } => {
+                let condition = self.read(condition)?;
+                let address = self.read(address)?;
+                if 0 != condition {
+                    self.ip = self.location(address)?;
+                } else {
+                    self.ip += 3;
+                }
+            },
+            Instruction::Multiply { op1, op2, address } => {
+                let op1 = self.read(op1)?;
+                let op2 = self.read(op2)?;
+                let address = self.address(address)?;
+                self.store(address, op1.wrapping_mul(op2));
+                self.ip += 4;
+            },
+            Instruction::Output(op1) => {
+                let value = self.read(op1)?;
+                self.ip += 2;
+                self.steps += 1;
+                return Ok(Some(RunState::Output(value)));
+            },
+            Instruction::TestEqual { op1, op2, address } => {
+                let op1 = self.read(op1)?;
+                let op2 = self.read(op2)?;
+                let address = self.address(address)?;
+                self.store(address, if op1 == op2 { 1 } else { 0 });
+                self.ip += 4;
+            },
+            Instruction::TestLessThan { op1, op2, address } => {
+                let op1 = self.read(op1)?;
+                let op2 = self.read(op2)?;
+                let address = self.address(address)?;
+                self.store(address, if op1 < op2 { 1 } else { 0 });
+                self.ip += 4;
+            },
+        }
+        self.steps += 1;
+        Ok(None)
+    }
+
+    // Compare the state the last step left the machine in with the saved
+    // one, failing if they're the same, and save it if it's time to.
+    fn check_for_loop(&mut self) -> Result<(), IntcodeError> {
+        let Self { loops, mem, ip, relative_base, input, steps, .. } = self;
+        let detector = match loops {
+            Some(detector) => detector,
+            None => return Ok(()),
+        };
+        match &mut detector.saved {
+            Some(saved) => {
+                if saved.ip == *ip && saved.relative_base == *relative_base
+                        && saved.input == *input && saved.mem == *mem {
+                    let start = saved.steps;
+                    return Err(IntcodeError::InfiniteLoop {
+                        ip: self.ip,
+                        opcode: self.opcode(),
+                        start,
+                        length: self.steps - start,
+                    });
+                }
+                if *steps - saved.steps < detector.power {
+                    return Ok(());
+                }
+                detector.power *= 2;
+                saved.ip = *ip;
+                saved.relative_base = *relative_base;
+                saved.mem.clone_from(mem);
+                saved.input.clone_from(input);
+                saved.steps = *steps;
+            },
+            None => {
+                detector.power = 1;
+                detector.saved = Some(SavedState {
+                    ip: *ip,
+                    relative_base: *relative_base,
+                    mem: mem.clone(),
+                    input: input.clone(),
+                    steps: *steps,
+                });
+            },
+        }
+        Ok(())
+    }
+
+    // Forget the state loop detection saved, after the machine was changed
+    // from outside.
+    fn forget_states(&mut self) {
+        if let Some(detector) = &mut self.loops {
+            *detector = LoopDetector::default();
+        }
+    }
+
+    fn device_error(&self, ip: usize, e: io::Error) -> IntcodeError {
+        let opcode = self.mem.get(ip).copied().unwrap_or(0);
+        let unparsable = e
+            .get_ref()
+            .and_then(|e| e.downcast_ref::<UnparsableInput>());
+        match unparsable {
+            Some(UnparsableInput(input)) => IntcodeError::UnparsableInput {
+                ip,
+                opcode,
+                input: input.clone(),
+            },
+            None => IntcodeError::Device {
+                ip,
+                opcode,
+                message: e.to_string(),
+            },
+        }
+    }
+
+    // The opcode word of the current instruction, for error reporting.
+    fn opcode(&self) -> i64 {
+        self.mem.get(self.ip).copied().unwrap_or(0)
+    }
+
+    fn read(&mut self, op: Operand) -> Result<i64, IntcodeError> {
+        let address = match op {
+            Operand::Immediate(v) => return Ok(v),
+            op => self.address(op)?,
+        };
+        Ok(*self.cell(address))
+    }
+
+    // Resolve an operand that refers to memory.
+    fn address(&self, op: Operand) -> Result<usize, IntcodeError> {
+        match op {
+            Operand::Address(p) => self.location(p),
+            Operand::Immediate(_) => Err(IntcodeError::ImmediateModeWrite {
+                ip: self.ip,
+                opcode: self.opcode(),
+            }),
+            Operand::Relative(offset) => {
+                self.location(self.relative_base.wrapping_add(offset))
+            },
+        }
+    }
+
+    // Check that an address lies within the machine's memory limit.
+    fn location(&self, address: i64) -> Result<usize, IntcodeError> {
+        if address < 0 {
+            Err(IntcodeError::NegativeAddress {
+                ip: self.ip,
+                opcode: self.opcode(),
+                address,
+            })
+        } else if self.memory_limit as u64 <= address as u64 {
+            Err(IntcodeError::AddressOutOfBounds {
+                ip: self.ip,
+                opcode: self.opcode(),
+                address,
+            })
+        } else {
+            Ok(address as usize)
+        }
+    }
+
+    // Get a memory cell, growing memory to include it if necessary.
+    fn cell(&mut self, address: usize) -> &mut i64 {
+        if self.mem.len() <= address {
+            self.mem.resize(address + 1, 0);
+        }
+        &mut self.mem[address]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pauses_for_input_and_output() {
+        // Echo a single value, then halt.
+        let mut vm = Intcode::new(vec![3, 0, 4, 0, 99]);
+        assert_eq!(Ok(RunState::NeedsInput), vm.run());
+        vm.push_input(42);
+        assert_eq!(Ok(RunState::Output(42)), vm.run());
+        assert_eq!(Ok(RunState::Halted), vm.run());
+        assert_eq!(Ok(RunState::Halted), vm.run());
+    }
+
+    #[test]
+    fn collects_output_until_blocked() {
+        let mut vm = Intcode::new(vec![104, 1, 104, 2, 3, 0, 99]);
+        assert_eq!(Ok(RunState::NeedsInput), vm.run_until_blocked());
+        assert_eq!(vec![1, 2], vm.drain_output());
+        vm.push_input(7);
+        assert_eq!(Ok(RunState::Halted), vm.run_until_blocked());
+        assert_eq!(7, vm.memory()[0]);
+    }
+
+    #[test]
+    fn quine() {
+        let program = vec![
+            109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101,
+            0, 99,
+        ];
+        let mut vm = Intcode::new(program.clone());
+        assert_eq!(Ok(program), vm.run_to_halt());
+    }
+
+    #[test]
+    fn large_numbers() {
+        let program = vec![1102, 34915192, 34915192, 7, 4, 7, 99, 0];
+        let mut vm = Intcode::new(program);
+        assert_eq!(Ok(RunState::Output(1219070632396864)), vm.run());
+        let mut vm = Intcode::new(vec![104, 1125899906842624, 99]);
+        assert_eq!(Ok(RunState::Output(1125899906842624)), vm.run());
+    }
+
+    #[test]
+    fn memory_grows_on_access() {
+        // Copy address 1000 (initially 0) into address 500.
+        let mut vm = Intcode::new(vec![1001, 1000, 5, 500, 99]);
+        assert_eq!(Ok(RunState::Halted), vm.run());
+        assert_eq!(5, vm.memory()[500]);
+        assert_eq!(1001, vm.memory().len());
+    }
+
+    #[test]
+    fn cache_forgets_overwritten_code() {
+        // Count down from 3 by decrementing the immediate operand of the
+        // OUT at address 0, then overwrite that OUT with a Halt and jump
+        // back to it.
+        let program = vec![
+            104, 3, 1001, 1, -1, 1, 1005, 1, 0, 1101, 0, 99, 0, 1105, 1, 0,
+        ];
+        let mut reference = Intcode::new(program.clone());
+        let mut cached = Intcode::new(program.clone());
+        cached.set_engine(Engine::Cached);
+        assert_eq!(Ok(vec![3, 2, 1]), reference.run_to_halt());
+        assert_eq!(Ok(vec![3, 2, 1]), cached.run_to_halt());
+        assert_eq!(reference.memory(), cached.memory());
+        // Resetting must also forget whatever the last run rewrote.
+        let mut patched = program;
+        patched[1] = 2;
+        cached.reset(&patched);
+        assert_eq!(Ok(vec![2, 1]), cached.run_to_halt());
+    }
+
+    #[test]
+    fn reset_restores_the_image() {
+        let program = [3, 0, 4, 0, 99];
+        let mut vm = Intcode::new(program.to_vec());
+        vm.push_input(7);
+        vm.push_input(8);
+        assert_eq!(Ok(vec![7]), vm.run_to_halt());
+        vm.reset(&program);
+        assert_eq!(&program, vm.memory());
+        assert_eq!(0, vm.ip());
+        assert_eq!(Ok(RunState::NeedsInput), vm.run());
+    }
+
+    #[test]
+    fn errors_carry_context() {
+        let mut vm = Intcode::new(vec![1101, 1, 1, 0, 42]);
+        assert_eq!(Err(IntcodeError::UnknownOpcode { ip: 4, opcode: 42 }),
+            vm.run());
+        let mut vm = Intcode::new(vec![1, 0, 0, 0, 305, 1, 99]);
+        assert_eq!(Err(IntcodeError::InvalidParameterMode {
+            ip: 4,
+            opcode: 305,
+            parameter: 1,
+            mode: 3,
+        }), vm.run());
+        let mut vm = Intcode::new(vec![11101, 1, 1, 0, 99]);
+        assert_eq!(Err(IntcodeError::ImmediateModeWrite {
+            ip: 0,
+            opcode: 11101,
+        }), vm.run());
+        let mut vm = Intcode::new(vec![1, -1, 0, 0, 99]);
+        assert_eq!(Err(IntcodeError::NegativeAddress {
+            ip: 0,
+            opcode: 1,
+            address: -1,
+        }), vm.run());
+        let mut vm = Intcode::new(vec![1, 100, 0, 0, 99]);
+        vm.set_memory_limit(10);
+        assert_eq!(Err(IntcodeError::AddressOutOfBounds {
+            ip: 0,
+            opcode: 1,
+            address: 100,
+        }), vm.run());
+        // Running off the end of memory reports the word that's there.
+        let mut vm = Intcode::new(vec![104, 1, 104, 2, 99]);
+        vm.set_memory_limit(4);
+        assert_eq!(Err(IntcodeError::AddressOutOfBounds {
+            ip: 4,
+            opcode: 99,
+            address: 4,
+        }), vm.run_to_halt());
+        // So does patching memory there.
+        assert_eq!(Err(IntcodeError::AddressOutOfBounds {
+            ip: 4,
+            opcode: 99,
+            address: 4,
+        }), vm.poke(4, 0));
+        assert_eq!(Ok(()), vm.poke(3, 0));
+    }
+
+    #[test]
+    fn ignores_modes_past_the_last_parameter() {
+        let mut vm = Intcode::new(vec![30104, 7, 99]);
+        assert_eq!(Ok(vec![7]), vm.run_to_halt());
+        let mut vm = Intcode::new(vec![39999]);
+        assert_eq!(Ok(vec![]), vm.run_to_halt());
+    }
+
+    #[test]
+    fn input_errors() {
+        let mut vm = Intcode::new(vec![3, 0, 99]);
+        assert_eq!(Err(IntcodeError::InputExhausted { ip: 0, opcode: 3 }),
+            vm.run_to_halt());
+        assert_eq!(Err(IntcodeError::UnparsableInput {
+            ip: 0,
+            opcode: 3,
+            input: "x".to_string(),
+        }), vm.push_input_str("x"));
+        assert_eq!(Ok(()), vm.push_input_str("-5\n"));
+        assert_eq!(Ok(Vec::new()), vm.run_to_halt());
+        assert_eq!(-5, vm.memory()[0]);
+    }
+
+    #[test]
+    fn step_limit_pauses_and_resumes() {
+        let mut vm = Intcode::new(vec![104, 1, 104, 2, 99]);
+        vm.set_step_limit(Some(1));
+        assert_eq!(Ok(RunState::Output(1)), vm.run());
+        assert_eq!(Ok(RunState::BudgetExhausted), vm.run());
+        assert_eq!(Ok(RunState::BudgetExhausted), vm.run());
+        vm.set_step_limit(Some(5));
+        assert_eq!(Ok(RunState::Output(2)), vm.run());
+        assert_eq!(Ok(RunState::Halted), vm.run());
+        // JT #1, #0 jumps to itself forever.
+        let mut vm = Intcode::new(vec![1105, 1, 0]);
+        vm.set_step_limit(Some(10));
+        assert_eq!(Err(IntcodeError::BudgetExhausted {
+            ip: 0,
+            opcode: 1105,
+            limit: 10,
+        }), vm.run_to_halt());
+        assert_eq!(10, vm.steps());
+    }
+
+    #[test]
+    fn detects_infinite_loops() {
+        // Output 7, then jump back to the output forever.
+        let mut vm = Intcode::new(vec![104, 7, 1105, 1, 0]);
+        vm.set_loop_detection(true);
+        assert_eq!(Ok(RunState::Output(7)), vm.run());
+        assert_eq!(Ok(RunState::Output(7)), vm.run());
+        assert_eq!(Err(IntcodeError::InfiniteLoop {
+            ip: 0,
+            opcode: 104,
+            start: 2,
+            length: 2,
+        }), vm.run());
+        // Echo input forever. Waiting for input, even for the same value
+        // again, isn't a loop.
+        let mut vm = Intcode::new(vec![3, 9, 4, 9, 1105, 1, 0, 99, 0, 0]);
+        vm.set_loop_detection(true);
+        for _ in 0..2 {
+            assert_eq!(Ok(RunState::NeedsInput), vm.run());
+            assert_eq!(Ok(RunState::NeedsInput), vm.run());
+            vm.push_input(5);
+            assert_eq!(Ok(RunState::Output(5)), vm.run());
+        }
+    }
+
+    #[test]
+    fn only_reports_loops_that_repeat_exactly() {
+        // Count mem[20] up to 1000, then either halt or jump to the jump
+        // forever. No state repeats while counting.
+        let program = |end| {
+            let mut program = vec![1001, 20, 1, 20, 1008, 20, 1000, 21,
+                1006, 21, 0, 1105, 1, 11, 0, 0, 0, 0, 0, 0, 0, 0];
+            if end {
+                program[11] = 99;
+            }
+            program
+        };
+        let mut vm = Intcode::new(program(true));
+        vm.set_loop_detection(true);
+        assert_eq!(Ok(Vec::new()), vm.run_to_halt());
+        assert_eq!(3000, vm.steps());
+        let mut vm = Intcode::new(program(false));
+        vm.set_loop_detection(true);
+        match vm.run_to_halt() {
+            Err(IntcodeError::InfiniteLoop { ip, start, length, .. }) => {
+                assert_eq!((11, 1), (ip, length));
+                assert!(3000 <= start && vm.steps() < 2*3000 + 2);
+            },
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn tracer_sees_every_step() {
+        use std::sync::{Arc, Mutex};
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        let mut vm = Intcode::new(vec![1, 0, 0, 0, 1101, 2, 3, 0, 99]);
+        vm.set_tracer(move |event| sink.lock().unwrap().push(event.clone()));
+        assert_eq!(Ok(RunState::Halted), vm.run());
+        let events = events.lock().unwrap();
+        assert_eq!(3, events.len());
+        assert_eq!(vec![1, 1, 0], events[0].values);
+        assert_eq!(Some(MemoryWrite { address: 0, old: 1, new: 2 }),
+            events[0].write);
+        assert_eq!(4, events[1].ip);
+        assert_eq!(vec![2, 3, 0], events[1].values);
+        assert_eq!(Instruction::Halt, events[2].instruction);
+    }
+
+    #[test]
+    fn tracing_fails_like_an_untraced_step() {
+        // The first reads a source far past the end of memory before its
+        // destination fails; the second fails on both, and the source comes
+        // first.
+        for program in &[vec![1, 50, 0, -1, 99], vec![1, -5, 0, 200, 99]] {
+            let mut plain = Intcode::new(program.clone());
+            let mut traced = Intcode::new(program.clone());
+            plain.set_memory_limit(100);
+            traced.set_memory_limit(100);
+            traced.set_tracer(|_| {});
+            let error = plain.step().unwrap_err();
+            assert_eq!(Err(error), traced.step());
+            assert_eq!(plain.memory(), traced.memory());
+            assert_eq!(0, traced.ip());
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    rc::Rc,
+    str::FromStr,
+};
+
+use aoc_common::{ParseError, parse::{self, Cursor}};
+
+#[derive(Debug, Eq, PartialEq)]
+struct HeavenlyBody {
+    name: String,
+    depth: Option<usize>,
+    children: Vec<Rc<RefCell<HeavenlyBody>>>,
+}
+
+impl HeavenlyBody {
+    fn new(name: String) -> HeavenlyBody {
+        Self {
+            name,
+            depth: None,
+            children: Vec::new(),
+        }
+    }
+
+    fn add_child(&mut self, hb: Rc<RefCell<HeavenlyBody>>) {
+        self.children.push(hb);
+    }
+}
+
+fn set_depths(root: &Rc<RefCell<HeavenlyBody>>, depth: usize) {
+    let mut root = root.borrow_mut();
+    root.depth = Some(depth);
+    for hb in root.children.iter() {
+        set_depths(hb, depth + 1);
+    }
+}
+
+fn orbit_checksum(root: &Rc<RefCell<HeavenlyBody>>) -> usize {
+    let root = root.borrow();
+    let mut acc = 0;
+    for hb in root.children.iter() {
+        acc += orbit_checksum(hb);
+    }
+    acc + root.depth.unwrap()
+}
+
+fn path_to_body(root: &Rc<RefCell<HeavenlyBody>>, name: &str, path: &mut Vec<String>) -> bool {
+    let root = root.borrow();
+    if root.name == name {
+        path.push(name.to_string());
+        return true;
+    }
+    for hb in root.children.iter() {
+        if path_to_body(hb, name, path) {
+            path.push(root.name.to_string());
+            return true;
+        }
+    }
+    false
+}
+
+// One line of the map: the child orbits the parent.
+struct Orbit {
+    parent: String,
+    child: String,
+}
+
+// Valid string (e.g.): "COM)B".
+impl FromStr for Orbit {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cursor = Cursor::new(s.trim_end());
+        cursor.skip_whitespace();
+        let name = |cursor: &mut Cursor<'_>| {
+            match cursor.take_while(|c| ')' != c) {
+                "" => Err(cursor.error("a name")),
+                name => Ok(name.to_string()),
+            }
+        };
+        let parent = name(&mut cursor)?;
+        cursor.expect(")")?;
+        let child = name(&mut cursor)?;
+        cursor.end()?;
+        Ok(Self { parent, child })
+    }
+}
+
+// An error for a map that's missing `name`.
+fn missing(input: &str, name: &str) -> ParseError {
+    ParseError::new(1, format!("an orbit of {}", name))
+        .found("the end of the input")
+        .at_line(input.lines().count() + 1)
+}
+
+fn get_orbits(input: &str)
+        -> Result<HashMap<String, Rc<RefCell<HeavenlyBody>>>, ParseError> {
+    let mut hm = HashMap::new();
+    for orbit in parse::lines::<Orbit>(input)? {
+        let parent_name = orbit.parent.as_str();
+        let child_name = orbit.child.as_str();
+        if !hm.contains_key(child_name) {
+            let child = Rc::new(RefCell::new(HeavenlyBody::new(child_name.to_string())));
+            hm.insert(child_name.to_string(), child);
+        }
+        let child = hm.get(child_name).unwrap();
+        if let Some(parent) = hm.get(parent_name) {
+            parent.borrow_mut().add_child(child.clone());
+        } else {
+            let parent = Rc::new(RefCell::new(HeavenlyBody::new(parent_name.to_string())));
+            parent.borrow_mut().add_child(child.clone());
+            hm.insert(parent_name.to_string(), parent);
+        }
+    }
+    let root = hm.get("COM").ok_or_else(|| missing(input, "COM"))?;
+    set_depths(root, 0);
+    Ok(hm)
+}
+
+fn get_distance(root: &Rc<RefCell<HeavenlyBody>>) -> usize {
+    let mut path_you = Vec::new();
+    path_to_body(root, "YOU", &mut path_you);
+    let mut path_san = Vec::new();
+    path_to_body(root, "SAN", &mut path_san);
+    let mut hs = HashSet::new();
+    for elem in path_you.iter() {
+        hs.insert(elem.clone());
+    }
+    for elem in path_san.iter() {
+        hs.insert(elem.clone());
+    }
+    for (elem_you, elem_san) in path_you.into_iter().rev().zip(path_san.into_iter().rev()) {
+        if elem_you == elem_san {
+            hs.remove(&elem_you);
+        }
+    }
+    hs.len() - 2
+}
+
+/// The total number of direct and indirect orbits.
+pub fn total_orbits(input: &str) -> Result<usize, ParseError> {
+    let hm = get_orbits(input)?;
+    Ok(orbit_checksum(&hm["COM"]))
+}
+
+/// The number of orbital transfers needed to get from the object YOU orbit
+/// to the object SAN orbits.
+pub fn transfers_to_santa(input: &str) -> Result<usize, ParseError> {
+    let hm = get_orbits(input)?;
+    for &name in ["YOU", "SAN"].iter() {
+        if !hm.contains_key(name) {
+            return Err(missing(input, name));
+        }
+    }
+    Ok(get_distance(&hm["COM"]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test0() {
+        let hm = get_orbits(&fs::read_to_string("test0.txt").unwrap())
+            .unwrap();
+        let root = hm.get("COM").unwrap();
+        assert_eq!(42, orbit_checksum(root));
+    }
+
+    #[test]
+    fn test1() {
+        let hm = get_orbits(&fs::read_to_string("test1.txt").unwrap())
+            .unwrap();
+        let root = hm.get("COM").unwrap();
+        assert_eq!(4, get_distance(root));
+    }
+
+    #[test]
+    fn reports_malformed_maps() {
+        assert_eq!(Ok(3), total_orbits("COM)B\n\nB)C\n"));
+        let error = |result: Result<usize, ParseError>| {
+            result.unwrap_err().to_string()
+        };
+        assert_eq!("2:3: expected ')', found the end of the line",
+            error(total_orbits("COM)B\nBC\n")));
+        assert_eq!("1:5: expected a name, found the end of the line",
+            error(total_orbits("COM)")));
+        assert_eq!("1:6: expected the end of the line, found ')'",
+            error(total_orbits("COM)B)C")));
+        assert_eq!("3:1: expected an orbit of COM, found the end of the \
+            input", error(total_orbits("A)B\nB)C\n")));
+        assert_eq!("1:1: expected an orbit of COM, found the end of the \
+            input", error(total_orbits("")));
+        assert_eq!("2:1: expected an orbit of SAN, found the end of the \
+            input", error(transfers_to_santa("COM)YOU")));
+    }
+}
@@ -0,0 +1,61 @@
+use std::error::Error;
+
+pub use intcode::amplifier::Wiring;
+use intcode::amplifier::max_thruster_signal;
+
+/// Find the phase sequence drawn from `phase_set` that produces the highest
+/// thrust signal, returning it with the signal.
+pub fn solve(program: &[i64], phase_set: &[i64], wiring: Wiring)
+        -> Result<(Vec<i64>, i64), Box<dyn Error>> {
+    let best = max_thruster_signal(program, phase_set, wiring)?;
+    Ok(best.ok_or("No phase sequence produced a thrust signal.")?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use intcode::parse_source;
+
+    fn solve_file(path: &str, phase_set: &[i64], wiring: Wiring)
+            -> Result<(Vec<i64>, i64), Box<dyn Error>> {
+        solve(&parse_source(Path::new(path))?, phase_set, wiring)
+    }
+
+    #[test]
+    fn day7_part1_tests() {
+        let cases = [
+            ("input/day7-part1-test0.txt", vec![4, 3, 2, 1, 0], 43210),
+            ("input/day7-part1-test1.txt", vec![0, 1, 2, 3, 4], 54321),
+            ("input/day7-part1-test2.txt", vec![1, 0, 4, 3, 2], 65210),
+        ];
+        for (path, phases, signal) in cases.iter() {
+            let best = solve_file(path, &[0, 1, 2, 3, 4], Wiring::Series)
+                .unwrap();
+            assert_eq!((phases.clone(), *signal), best);
+        }
+    }
+
+    #[test]
+    fn day7_part2_tests() {
+        let cases = [
+            ("input/day7-part2-test0.txt", vec![9, 8, 7, 6, 5], 139629729),
+            ("input/day7-part2-test1.txt", vec![9, 7, 8, 5, 6], 18216),
+        ];
+        for (path, phases, signal) in cases.iter() {
+            let best = solve_file(path, &[5, 6, 7, 8, 9], Wiring::Feedback)
+                .unwrap();
+            assert_eq!((phases.clone(), *signal), best);
+        }
+    }
+
+    #[test]
+    fn day7_answers() {
+        let (_, signal) = solve_file("input/day7-part1-input.txt",
+            &[0, 1, 2, 3, 4], Wiring::Series).unwrap();
+        assert_eq!(398674, signal);
+        let (_, signal) = solve_file("input/day7-part2-input.txt",
+            &[5, 6, 7, 8, 9], Wiring::Feedback).unwrap();
+        assert_eq!(39431233, signal);
+    }
+}
@@ -1,11 +1,12 @@
 use std::{
-    fs,
     ops::{
         Index,
         IndexMut,
     },
 };
 
+use aoc_common::ParseError;
+
 // An Image is simply a 3-dimensional vector with layers, rows, and columns as the coordinates.
 #[derive(Debug)]
 struct Image(Vec<Vec<Vec<u8>>>);
@@ -52,7 +53,7 @@ impl Image {
         let height = self[0].len();
         let width  = self[0][0].len();
         let mut layer_with_fewest_0s = 0;
-        let mut count_min = usize::max_value();
+        let mut count_min = usize::MAX;
         for layer in 0..layers {
             let mut count_0s = 0;
             for row in 0..height {
@@ -152,35 +153,71 @@ impl Image {
     }
 }
 
-fn main() {
-    const IMG_WIDTH: usize = 25;
-    const IMG_HEIGHT: usize = 6;
-    let input = fs::read_to_string("input.txt")
-        .expect("Error reading file into string.");
-    let n_layers = input.len()/(IMG_WIDTH*IMG_HEIGHT);
-    let image = Image::new(n_layers, IMG_HEIGHT, IMG_WIDTH, &input);
+pub const IMG_WIDTH: usize = 25;
+pub const IMG_HEIGHT: usize = 6;
+
+// Parse an image of at least one whole layer, all digits on one line.
+fn image(input: &str) -> Result<Image, ParseError> {
+    let input = input.trim_end();
+    if let Some((column, c)) = input
+            .chars()
+            .enumerate()
+            .find(|&(_, c)| !c.is_ascii_digit()) {
+        return Err(ParseError::new(column + 1, "a digit")
+            .found(format!("{:?}", c))
+            .at_line(1));
+    }
+    let layer = IMG_WIDTH*IMG_HEIGHT;
+    let n_layers = input.len()/layer;
+    let left_over = input.len()%layer;
+    if 0 == n_layers || 0 != left_over {
+        let found = match left_over {
+            0 => "the end of the line".to_string(),
+            1 => "1 digit".to_string(),
+            digits => format!("{} digits", digits),
+        };
+        return Err(ParseError::new(n_layers*layer + 1,
+            format!("a layer of {} digits", layer)).found(found).at_line(1));
+    }
+    Ok(Image::new(n_layers, IMG_HEIGHT, IMG_WIDTH, input))
+}
+
+/// The number of 1 digits multiplied by the number of 2 digits on the layer
+/// with the fewest 0 digits.
+pub fn checksum(input: &str) -> Result<usize, ParseError> {
+    let image = image(input)?;
     let fewest_layer = image.find_layer_with_fewest_0s();
     let ones = image.count_n_digits_on_layer(1, fewest_layer);
     let twos = image.count_n_digits_on_layer(2, fewest_layer);
-    println!("Part 1: {}", ones*twos);
-    println!("Part 2:\n{}", image.decode());
+    Ok(ones*twos)
+}
+
+/// The message the image shows once its layers are stacked.
+pub fn message(input: &str) -> Result<String, ParseError> {
+    Ok(image(input)?.decode())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
 
     #[test]
     fn part1() {
-        const IMG_WIDTH: usize = 25;
-        const IMG_HEIGHT: usize = 6;
         let input = fs::read_to_string("input.txt")
             .expect("Error reading file into string.");
-        let n_layers = input.len()/(IMG_WIDTH*IMG_HEIGHT);
-        let image = Image::new(n_layers, IMG_HEIGHT, IMG_WIDTH, &input);
-        let fewest_layer = image.find_layer_with_fewest_0s();
-        let ones = image.count_n_digits_on_layer(1, fewest_layer);
-        let twos = image.count_n_digits_on_layer(2, fewest_layer);
-        assert_eq!(1690, ones*twos);
+        assert_eq!(Ok(1690), checksum(&input));
+    }
+
+    #[test]
+    fn reports_malformed_images() {
+        let error = |input: &str| checksum(input).unwrap_err().to_string();
+        assert_eq!("1:1: expected a layer of 150 digits, found the end of \
+            the line", error("\n"));
+        let layer = "0".repeat(150);
+        assert_eq!("1:151: expected a layer of 150 digits, found 2 digits",
+            error(&format!("{}12", layer)));
+        assert_eq!("1:3: expected a digit, found 'x'", error("12x"));
+        assert_eq!(Ok(0), checksum(&layer));
     }
 }
@@ -0,0 +1,271 @@
+//! The registry of known answers, which `aoc verify` checks the solutions
+//! against.
+//!
+//! The registry is a text file with one answer per line:
+//!
+//! ```text
+//! <year> <day> <part> <input> <answer>
+//! ```
+//!
+//! `<input>` is written the way `aoc list` shows it: a path relative to the
+//! root of the repository, or inline text in double quotes. The answer is
+//! the rest of the line. An answer that spans several lines is written in
+//! double quotes, with `\n` for each line break. Blank lines and lines
+//! starting with `#` are ignored.
+
+use std::{
+    collections::HashSet,
+    error::Error,
+    fmt::{Display, Formatter, self},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use crate::{Input, Part, Solution, PARTS, SOLUTIONS, input};
+
+/// The registry's path, relative to the root of the repository.
+pub const REGISTRY: &str = "answers.txt";
+
+/// The registry's path.
+pub fn path() -> PathBuf {
+    crate::root().join(REGISTRY)
+}
+
+/// The known answer to one part of a puzzle, for one input.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Entry<'a> {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub input: Input<'a>,
+    pub answer: String,
+}
+
+impl Display for Entry<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day {:>2} part {} ({})", self.year, self.day,
+            self.part, self.input)
+    }
+}
+
+/// A line of the registry that couldn't be parsed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegistryError {
+    /// The line number, starting from 1.
+    pub line: usize,
+    pub reason: String,
+}
+
+impl Display for RegistryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", REGISTRY, self.line, self.reason)
+    }
+}
+
+impl Error for RegistryError {}
+
+/// Parse the registry. Every year, day, part, and input may have only one
+/// answer.
+pub fn parse(text: &str) -> Result<Vec<Entry<'_>>, RegistryError> {
+    let mut entries: Vec<Entry<'_>> = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let error = |reason: String| RegistryError { line: index + 1, reason };
+        let entry = parse_entry(line).map_err(error)?;
+        if entries.iter().any(|other| other.year == entry.year
+                && other.day == entry.day
+                && other.part == entry.part
+                && other.input == entry.input) {
+            return Err(error(format!("{} already has an answer", entry)));
+        }
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+fn parse_entry(line: &str) -> Result<Entry<'_>, String> {
+    let mut rest = line;
+    let mut field = |name: &str| {
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let (field, after) = rest.split_at(end);
+        rest = after.trim_start();
+        if field.is_empty() {
+            Err(format!("missing {}", name))
+        } else {
+            Ok(field)
+        }
+    };
+    let year = field("year")?;
+    let year = year.parse().map_err(|_| format!("invalid year {:?}", year))?;
+    let day = field("day")?;
+    let day = day.parse().map_err(|_| format!("invalid day {:?}", day))?;
+    let part = field("part")?.parse()?;
+    let input = Input::parse(field("input")?);
+    let answer = rest;
+    if answer.is_empty() {
+        return Err("missing answer".to_string());
+    }
+    let answer = if answer.starts_with('"') {
+        unquote(answer).ok_or(format!("badly quoted answer {}", answer))?
+    } else {
+        answer.to_string()
+    };
+    Ok(Entry { year, day, part, input, answer })
+}
+
+// Undo the quoting of a multi-line answer.
+fn unquote(s: &str) -> Option<String> {
+    let s = s.strip_prefix('"')?.strip_suffix('"')?;
+    let mut unquoted = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                'n' => unquoted.push('\n'),
+                c @ '\\' | c @ '"' => unquoted.push(c),
+                _ => return None,
+            },
+            '"' => return None,
+            c => unquoted.push(c),
+        }
+    }
+    Some(unquoted)
+}
+
+/// How a solution fared against a known answer.
+#[derive(Debug)]
+pub enum Verdict {
+    Pass,
+    /// The solution gave a different answer.
+    Fail(String),
+    /// The solution couldn't be run, or returned an error.
+    Error(Box<dyn Error>),
+}
+
+/// Run the solution for `entry` and compare its answer with the known one.
+/// Also returns how long the solution took, not counting reading the input.
+pub fn verify(entry: &Entry<'_>) -> (Verdict, Duration) {
+    let solution = match crate::find(entry.year, entry.day) {
+        Some(solution) => solution,
+        None => {
+            let error = format!("no solution for {} day {}", entry.year,
+                entry.day);
+            return (Verdict::Error(error.into()), Duration::default());
+        },
+    };
+    let input = match entry.input.read() {
+        Ok(input) => input,
+        Err(e) => {
+            let error = format!("{}: {}", entry.input, e);
+            return (Verdict::Error(error.into()), Duration::default());
+        },
+    };
+    let start = Instant::now();
+    let answer = solution.solve(entry.part, &input);
+    let elapsed = start.elapsed();
+    let verdict = match answer {
+        Ok(answer) => {
+            let answer = answer.to_string();
+            if answer == entry.answer {
+                Verdict::Pass
+            } else {
+                Verdict::Fail(answer)
+            }
+        },
+        Err(e) => Verdict::Error(input::locate(e, entry.input.to_string())),
+    };
+    (verdict, elapsed)
+}
+
+/// The parts whose default input has no known answer.
+pub fn missing(entries: &[Entry<'_>])
+        -> Vec<(&'static dyn Solution, Part)> {
+    let known: HashSet<(u16, u8, Part, Input<'_>)> = entries
+        .iter()
+        .map(|entry| (entry.year, entry.day, entry.part, entry.input))
+        .collect();
+    let mut missing = Vec::new();
+    for &solution in SOLUTIONS {
+        for &part in PARTS.iter() {
+            let key = (solution.year(), solution.day(), part,
+                solution.input(part));
+            if !known.contains(&key) {
+                missing.push((solution, part));
+            }
+        }
+    }
+    missing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    #[test]
+    fn parses_entries() {
+        let text = "\
+# year day part input answer
+
+2015 4 1 \"bgvyzdsv\" 254575
+2019 2 1 2019/day2/input.txt 10566835
+2019 8 2 2019/day8/input.txt \"o  o\\n oo \\\\ \\\"\"
+";
+        let entries = parse(text).unwrap();
+        assert_eq!(vec![
+            Entry {
+                year: 2015,
+                day: 4,
+                part: Part::One,
+                input: Input::Text("bgvyzdsv"),
+                answer: "254575".to_string(),
+            },
+            Entry {
+                year: 2019,
+                day: 2,
+                part: Part::One,
+                input: Input::File("2019/day2/input.txt"),
+                answer: "10566835".to_string(),
+            },
+            Entry {
+                year: 2019,
+                day: 8,
+                part: Part::Two,
+                input: Input::File("2019/day8/input.txt"),
+                answer: "o  o\n oo \\ \"".to_string(),
+            },
+        ], entries);
+    }
+
+    #[test]
+    fn rejects_bad_entries() {
+        let line = |text| parse(text).unwrap_err().line;
+        assert_eq!(1, line("2019 2 1 2019/day2/input.txt"));
+        assert_eq!(1, line("2019 2 3 2019/day2/input.txt 1"));
+        assert_eq!(1, line("2019 x 1 2019/day2/input.txt 1"));
+        assert_eq!(2, line("# comment\n2019 8 2 input.txt \"o\\o\""));
+        assert_eq!(2, line("2019 2 1 input.txt 1\n2019 2 1 input.txt 2"));
+        assert!(parse("2019 2 1 input.txt 1\n2019 2 2 input.txt 2").is_ok());
+    }
+
+    #[test]
+    fn registry_is_complete() {
+        let text = fs::read_to_string(path()).unwrap();
+        let entries = parse(&text).unwrap();
+        for entry in &entries {
+            assert!(crate::find(entry.year, entry.day).is_some(), "{}",
+                entry);
+            assert!(entry.input.read().is_ok(), "{}", entry);
+        }
+        let missing: Vec<String> = missing(&entries)
+            .iter()
+            .map(|(solution, part)| format!("{} day {} part {}",
+                solution.year(), solution.day(), part))
+            .collect();
+        assert!(missing.is_empty(), "no answers for {:?}", missing);
+    }
+}
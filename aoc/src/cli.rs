@@ -0,0 +1,175 @@
+use std::path::PathBuf;
+
+use crate::Part;
+
+pub const USAGE: &str = "\
+Usage: aoc run <year> <day> [--part <1|2>] [--input <path|->]
+       aoc list
+       aoc verify [<year> [<day>]]
+       aoc profile [<year> [<day>]] [--json]";
+
+/// What `aoc` was asked to do.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Command {
+    /// Solve a day's puzzle. Both parts are solved unless `part` is given.
+    /// `input` is a path, or `-` for standard input; see `input::Source`
+    /// for where the input comes from when it's not given.
+    Run {
+        year: u16,
+        day: u8,
+        part: Option<Part>,
+        input: Option<PathBuf>,
+    },
+    /// List every solution with its default inputs.
+    List,
+    /// Check the solutions against the registry of known answers, either
+    /// all of them or only those for `year`, or `year` and `day`.
+    Verify {
+        year: Option<u16>,
+        day: Option<u8>,
+    },
+    /// Measure every part of every solution on its default input, or only
+    /// those for `year`, or `year` and `day`. The results are a table, or
+    /// JSON if `json` is set.
+    Profile {
+        year: Option<u16>,
+        day: Option<u8>,
+        json: bool,
+    },
+}
+
+/// Parse the command line arguments, without the program name.
+pub fn parse<I>(args: I) -> Result<Command, String>
+        where I: IntoIterator<Item = String> {
+    let mut args = args.into_iter();
+    match args.next().as_deref() {
+        Some("run") => (),
+        Some("list") => return match args.next() {
+            None => Ok(Command::List),
+            Some(arg) => Err(format!("unexpected argument {:?}", arg)),
+        },
+        Some("verify") => {
+            let (year, day, json) = parse_selection(args)?;
+            if json {
+                return Err("unexpected argument \"--json\"".to_string());
+            }
+            return Ok(Command::Verify { year, day });
+        },
+        Some("profile") => {
+            let (year, day, json) = parse_selection(args)?;
+            return Ok(Command::Profile { year, day, json });
+        },
+        Some(command) => return Err(format!("unknown command {:?}", command)),
+        None => return Err("missing command".to_string()),
+    }
+    let year = args.next().ok_or("missing year")?;
+    let year = year.parse().map_err(|_| format!("invalid year {:?}", year))?;
+    let day = args.next().ok_or("missing day")?;
+    let day = day.parse().map_err(|_| format!("invalid day {:?}", day))?;
+    let mut part = None;
+    let mut input = None;
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next().ok_or(format!("{} needs a value", arg))
+        };
+        match arg.as_str() {
+            "--part" => part = Some(value()?.parse()?),
+            "--input" => input = Some(PathBuf::from(value()?)),
+            _ => return Err(format!("unexpected argument {:?}", arg)),
+        }
+    }
+    Ok(Command::Run { year, day, part, input })
+}
+
+// Parse an optional year, then an optional day, then an optional `--json`.
+fn parse_selection<I>(args: I) -> Result<(Option<u16>, Option<u8>, bool),
+        String> where I: Iterator<Item = String> {
+    let mut year = None;
+    let mut day = None;
+    let mut json = false;
+    for arg in args {
+        if json {
+            return Err(format!("unexpected argument {:?}", arg));
+        } else if "--json" == arg {
+            json = true;
+        } else if year.is_none() {
+            year = Some(arg
+                .parse()
+                .map_err(|_| format!("invalid year {:?}", arg))?);
+        } else if day.is_none() {
+            day = Some(arg
+                .parse()
+                .map_err(|_| format!("invalid day {:?}", arg))?);
+        } else {
+            return Err(format!("unexpected argument {:?}", arg));
+        }
+    }
+    Ok((year, day, json))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn parses_run() {
+        assert_eq!(Ok(Command::Run {
+            year: 2019,
+            day: 12,
+            part: Some(Part::Two),
+            input: Some(PathBuf::from("path")),
+        }), parse(args("run 2019 12 --part 2 --input path")));
+        assert_eq!(Ok(Command::Run {
+            year: 2015,
+            day: 1,
+            part: None,
+            input: None,
+        }), parse(args("run 2015 1")));
+        assert_eq!(Ok(Command::List), parse(args("list")));
+    }
+
+    #[test]
+    fn parses_verify() {
+        assert_eq!(Ok(Command::Verify { year: None, day: None }),
+            parse(args("verify")));
+        assert_eq!(Ok(Command::Verify { year: Some(2019), day: None }),
+            parse(args("verify 2019")));
+        assert_eq!(Ok(Command::Verify { year: Some(2019), day: Some(7) }),
+            parse(args("verify 2019 7")));
+        assert!(parse(args("verify 2019 x")).is_err());
+        assert!(parse(args("verify 2019 7 1")).is_err());
+        assert!(parse(args("verify --json")).is_err());
+    }
+
+    #[test]
+    fn parses_profile() {
+        assert_eq!(Ok(Command::Profile { year: None, day: None, json: false }),
+            parse(args("profile")));
+        assert_eq!(Ok(Command::Profile {
+            year: Some(2015),
+            day: Some(4),
+            json: true,
+        }), parse(args("profile 2015 4 --json")));
+        assert_eq!(Ok(Command::Profile {
+            year: Some(2015),
+            day: None,
+            json: true,
+        }), parse(args("profile 2015 --json")));
+        assert!(parse(args("profile --json 2015")).is_err());
+        assert!(parse(args("profile 2015 4 --verbose")).is_err());
+    }
+
+    #[test]
+    fn rejects_bad_arguments() {
+        assert!(parse(args("")).is_err());
+        assert!(parse(args("run 2019")).is_err());
+        assert!(parse(args("run 2019 x")).is_err());
+        assert!(parse(args("run 2019 12 --part 3")).is_err());
+        assert!(parse(args("run 2019 12 --input")).is_err());
+        assert!(parse(args("run 2019 12 --verbose")).is_err());
+    }
+}
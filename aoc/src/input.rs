@@ -0,0 +1,202 @@
+//! Finding a solution's puzzle input.
+//!
+//! Every solution reads its input from the first of these that is given:
+//!
+//! 1. a path on the command line, with `--input <path>`;
+//! 2. standard input, with `--input -`;
+//! 3. a path in the `AOC_INPUT` environment variable;
+//! 4. the solution's default input, from `Solution::input`.
+//!
+//! Relative paths from the command line or the environment are relative to
+//! the current directory. Default input paths are relative to the root of
+//! the repository, so `aoc` finds them wherever it's run from.
+//!
+//! A solution only sees the text of its input, so a `ParseError` it returns
+//! is given the input's name afterwards, with `locate`.
+
+use std::{
+    error::Error,
+    ffi::OsString,
+    fmt::{Display, Formatter, self},
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use aoc_common::ParseError;
+
+use crate::Input;
+
+/// The environment variable naming an input file, for when the command
+/// line doesn't.
+pub const INPUT_VAR: &str = "AOC_INPUT";
+
+/// Where a solution's input comes from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Source {
+    /// A path given with `--input`.
+    Arg(PathBuf),
+    /// Standard input, asked for with `--input -`.
+    Stdin,
+    /// A path given in `AOC_INPUT`.
+    Var(PathBuf),
+    /// The solution's default input.
+    Default(Input<'static>),
+}
+
+impl Source {
+    /// Pick the source of a solution's input from the `--input` argument,
+    /// the value of `AOC_INPUT`, and the solution's default, in that order.
+    /// An empty `AOC_INPUT` counts as unset.
+    pub fn resolve(arg: Option<&Path>, var: Option<OsString>,
+            default: Input<'static>) -> Self {
+        match arg {
+            Some(path) if Path::new("-") == path => Self::Stdin,
+            Some(path) => Self::Arg(path.to_path_buf()),
+            None => match var {
+                Some(var) if !var.is_empty() => Self::Var(var.into()),
+                _ => Self::Default(default),
+            },
+        }
+    }
+
+    /// Read the input. Standard input can only be read once, so what was
+    /// read is kept in `stdin` for any later parts.
+    pub fn read(&self, stdin: &mut Option<String>)
+            -> Result<String, InputError> {
+        let error = |error| InputError { source: self.clone(), error };
+        match self {
+            Self::Arg(path) | Self::Var(path) => {
+                fs::read_to_string(path).map_err(error)
+            },
+            Self::Stdin => {
+                if stdin.is_none() {
+                    let mut text = String::new();
+                    io::stdin().read_to_string(&mut text).map_err(error)?;
+                    *stdin = Some(text);
+                }
+                Ok(stdin.clone().unwrap_or_default())
+            },
+            Self::Default(input) => input.read().map_err(error),
+        }
+    }
+
+    /// A short name for the input, to put in front of the line and column
+    /// of a parse error.
+    pub fn name(&self) -> String {
+        match self {
+            Self::Arg(path) | Self::Var(path) => path.display().to_string(),
+            Self::Stdin => "<stdin>".to_string(),
+            Self::Default(input) => input.to_string(),
+        }
+    }
+}
+
+/// Give a `ParseError` the name of the input it came from. Any other error
+/// is returned as it is.
+pub fn locate(error: Box<dyn Error>, name: String) -> Box<dyn Error> {
+    match error.downcast::<ParseError>() {
+        Ok(error) => Box::new(error.with_source(name)),
+        Err(error) => error,
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Arg(path) => write!(f, "{}", path.display()),
+            Self::Stdin => write!(f, "standard input"),
+            Self::Var(path) => {
+                write!(f, "{} (from {})", path.display(), INPUT_VAR)
+            },
+            Self::Default(input) => write!(f, "{} (the default input)", input),
+        }
+    }
+}
+
+/// An input that couldn't be read.
+#[derive(Debug)]
+pub struct InputError {
+    pub source: Source,
+    pub error: io::Error,
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.error.kind() {
+            io::ErrorKind::NotFound => {
+                write!(f, "input not found: {}", self.source)
+            },
+            _ => write!(f, "can't read input {}: {}", self.source, self.error),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEFAULT: Input<'static> = Input::File("2015/day1/input.txt");
+
+    #[test]
+    fn resolves_in_order() {
+        let arg = Some(Path::new("arg.txt"));
+        let stdin = Some(Path::new("-"));
+        let var = || Some(OsString::from("var.txt"));
+        assert_eq!(Source::Arg(PathBuf::from("arg.txt")),
+            Source::resolve(arg, var(), DEFAULT));
+        assert_eq!(Source::Stdin, Source::resolve(stdin, var(), DEFAULT));
+        assert_eq!(Source::Var(PathBuf::from("var.txt")),
+            Source::resolve(None, var(), DEFAULT));
+        assert_eq!(Source::Default(DEFAULT),
+            Source::resolve(None, Some(OsString::new()), DEFAULT));
+        assert_eq!(Source::Default(DEFAULT),
+            Source::resolve(None, None, DEFAULT));
+    }
+
+    #[test]
+    fn reads_sources() {
+        let mut stdin = None;
+        let text = Source::Default(DEFAULT).read(&mut stdin).unwrap();
+        assert!(text.starts_with('('));
+        let text = Source::Default(Input::Text("bgvyzdsv"))
+            .read(&mut stdin)
+            .unwrap();
+        assert_eq!("bgvyzdsv", text);
+        let mut stdin = Some("piped".to_string());
+        assert_eq!("piped", Source::Stdin.read(&mut stdin).unwrap());
+    }
+
+    #[test]
+    fn missing_files_name_their_source() {
+        let mut stdin = None;
+        let mut message = |source: Source| {
+            source.read(&mut stdin).unwrap_err().to_string()
+        };
+        assert_eq!("input not found: nope.txt",
+            message(Source::Arg(PathBuf::from("nope.txt"))));
+        assert_eq!("input not found: nope.txt (from AOC_INPUT)",
+            message(Source::Var(PathBuf::from("nope.txt"))));
+        assert_eq!("input not found: nope.txt (the default input)",
+            message(Source::Default(Input::File("nope.txt"))));
+    }
+
+    #[test]
+    fn locates_parse_errors() {
+        let error = ParseError::new(7, "'x='").at_line(3);
+        let name = Source::Arg(PathBuf::from("input.txt")).name();
+        assert_eq!("input.txt:3:7: expected 'x='",
+            locate(Box::new(error.clone()), name).to_string());
+        assert_eq!("<stdin>:3:7: expected 'x='",
+            locate(Box::new(error), Source::Stdin.name()).to_string());
+        let error = "The wires never cross.".into();
+        assert_eq!("The wires never cross.",
+            locate(error, Source::Stdin.name()).to_string());
+    }
+}
@@ -0,0 +1,176 @@
+//! Every Advent of Code solution in the repository, behind one interface.
+
+pub mod answers;
+pub mod cli;
+pub mod input;
+pub mod profile;
+mod y2015;
+mod y2019;
+
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, self},
+    fs,
+    io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// The answer to one part of a puzzle.
+pub type Answer = Box<dyn Display>;
+
+/// One half of a day's puzzle.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Part {
+    One,
+    Two,
+}
+
+pub const PARTS: [Part; 2] = [Part::One, Part::Two];
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::One => write!(f, "1"),
+            Self::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Self::One),
+            "2" => Ok(Self::Two),
+            _ => Err(format!("invalid part {:?}: expected 1 or 2", s)),
+        }
+    }
+}
+
+/// Where a part's puzzle input comes from.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Input<'a> {
+    /// A file, relative to the root of the repository.
+    File(&'a str),
+    /// Input short enough that the puzzle states it inline, such as a key or
+    /// a range.
+    Text(&'a str),
+}
+
+impl<'a> Input<'a> {
+    /// The inverse of `Display`: inline text is quoted, anything else is a
+    /// path.
+    pub fn parse(s: &'a str) -> Self {
+        match s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+            Some(text) => Self::Text(text),
+            None => Self::File(s),
+        }
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            Self::File(path) => fs::read_to_string(root().join(path)),
+            Self::Text(text) => Ok(text.to_string()),
+        }
+    }
+}
+
+impl Display for Input<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "{}", path),
+            Self::Text(text) => write!(f, "\"{}\"", text),
+        }
+    }
+}
+
+/// The root of the repository, which default input paths are relative to.
+pub fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+/// One day's puzzle, solved for any input.
+pub trait Solution: Sync {
+    fn year(&self) -> u16;
+    fn day(&self) -> u8;
+    /// The input `part` runs on when none is given.
+    fn input(&self, part: Part) -> Input<'static>;
+    fn part1(&self, input: &str) -> Result<Answer, Box<dyn Error>>;
+    fn part2(&self, input: &str) -> Result<Answer, Box<dyn Error>>;
+
+    fn solve(&self, part: Part, input: &str)
+            -> Result<Answer, Box<dyn Error>> {
+        match part {
+            Part::One => self.part1(input),
+            Part::Two => self.part2(input),
+        }
+    }
+}
+
+/// Every solution, in date order.
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &y2015::Day1,
+    &y2015::Day2,
+    &y2015::Day3,
+    &y2015::Day4,
+    &y2015::Day6,
+    &y2019::Day1,
+    &y2019::Day2,
+    &y2019::Day3,
+    &y2019::Day4,
+    &y2019::Day5,
+    &y2019::Day6,
+    &y2019::Day7,
+    &y2019::Day8,
+    &y2019::Day10,
+    &y2019::Day12,
+];
+
+/// The solution for a day, if there is one.
+pub fn find(year: u16, day: u8) -> Option<&'static dyn Solution> {
+    SOLUTIONS
+        .iter()
+        .copied()
+        .find(|solution| year == solution.year() && day == solution.day())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solutions_are_in_date_order() {
+        let dates: Vec<(u16, u8)> = SOLUTIONS
+            .iter()
+            .map(|solution| (solution.year(), solution.day()))
+            .collect();
+        let mut sorted = dates.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(sorted, dates);
+        assert!(find(2019, 12).is_some());
+        assert!(find(2019, 9).is_none());
+    }
+
+    #[test]
+    fn default_inputs_exist() {
+        for solution in SOLUTIONS {
+            for &part in PARTS.iter() {
+                let input = solution.input(part);
+                assert!(input.read().is_ok(), "{} day {} part {}: {}",
+                    solution.year(), solution.day(), part, input);
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_empty_keys() {
+        let day4 = find(2015, 4).unwrap();
+        for &part in PARTS.iter() {
+            let error = day4.solve(part, " \n").err().unwrap();
+            assert_eq!("The secret key is empty.", error.to_string());
+        }
+    }
+}
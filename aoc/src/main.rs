@@ -0,0 +1,153 @@
+use std::{
+    env,
+    error::Error,
+    fs,
+    process,
+};
+
+use aoc::{
+    PARTS,
+    SOLUTIONS,
+    answers::{self, Verdict},
+    cli::{self, Command, USAGE},
+    input::{self, INPUT_VAR, Source},
+    profile::{self, PeakAlloc},
+};
+
+#[global_allocator]
+static ALLOCATOR: PeakAlloc = PeakAlloc;
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("aoc: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), Box<dyn Error>> {
+    let command = match cli::parse(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("aoc: {}\n{}", e, USAGE);
+            process::exit(2);
+        },
+    };
+    match command {
+        Command::Run { year, day, part, input } => {
+            let solution = aoc::find(year, day)
+                .ok_or(format!("no solution for {} day {}", year, day))?;
+            let parts = match part {
+                Some(part) => vec![part],
+                None => PARTS.to_vec(),
+            };
+            let mut stdin = None;
+            for part in parts {
+                let source = Source::resolve(input.as_deref(),
+                    env::var_os(INPUT_VAR), solution.input(part));
+                let text = source.read(&mut stdin)?;
+                let answer = solution
+                    .solve(part, &text)
+                    .map_err(|e| input::locate(e, source.name()))?
+                    .to_string();
+                println!("{} day {} part {}:{}", year, day, part,
+                    on_own_line(&answer));
+            }
+        },
+        Command::List => {
+            for solution in SOLUTIONS {
+                let inputs: Vec<String> = PARTS
+                    .iter()
+                    .map(|&part| solution.input(part).to_string())
+                    .collect();
+                println!("{} day {:>2}: {}", solution.year(), solution.day(),
+                    inputs.join(", "));
+            }
+        },
+        Command::Verify { year, day } => {
+            let path = answers::path();
+            let text = fs::read_to_string(&path)
+                .map_err(|e| format!("{}: {}", path.display(), e))?;
+            let entries = answers::parse(&text)?;
+            let selected = |entry_year, entry_day| {
+                selects(year, day, entry_year, entry_day)
+            };
+            let mut failed = 0;
+            let mut passed = 0;
+            for entry in &entries {
+                if !selected(entry.year, entry.day) {
+                    continue;
+                }
+                let (verdict, elapsed) = answers::verify(entry);
+                match verdict {
+                    Verdict::Pass => {
+                        passed += 1;
+                        println!("pass    {:<58} {:>8.1?}", entry.to_string(),
+                            elapsed);
+                    },
+                    Verdict::Fail(answer) => {
+                        failed += 1;
+                        println!("FAIL    {:<58} {:>8.1?}", entry.to_string(),
+                            elapsed);
+                        println!("  expected:{}", on_own_line(&entry.answer));
+                        println!("  got:{}", on_own_line(&answer));
+                    },
+                    Verdict::Error(e) => {
+                        failed += 1;
+                        println!("FAIL    {}: {}", entry, e);
+                    },
+                }
+            }
+            let missing: Vec<_> = answers::missing(&entries)
+                .into_iter()
+                .filter(|(solution, _)| {
+                    selected(solution.year(), solution.day())
+                })
+                .collect();
+            for (solution, part) in &missing {
+                println!("missing {} day {:>2} part {} ({})", solution.year(),
+                    solution.day(), part, solution.input(*part));
+            }
+            println!("{} passed, {} failed, {} missing", passed, failed,
+                missing.len());
+            if 0 < failed {
+                process::exit(1);
+            }
+        },
+        Command::Profile { year, day, json } => {
+            let mut profiles = Vec::new();
+            for solution in SOLUTIONS {
+                if !selects(year, day, solution.year(), solution.day()) {
+                    continue;
+                }
+                for &part in PARTS.iter() {
+                    let input = solution.input(part);
+                    let text = Source::Default(input).read(&mut None)?;
+                    profiles.push(profile::profile(*solution, part,
+                        input.to_string(), &text)?);
+                }
+            }
+            if json {
+                print!("{}", profile::json(&profiles));
+            } else {
+                print!("{}", profile::table(&profiles));
+            }
+        },
+    }
+    Ok(())
+}
+
+// Whether a year and day are selected by an optional year and day.
+fn selects(year: Option<u16>, day: Option<u8>, solution_year: u16,
+        solution_day: u8) -> bool {
+    year.is_none_or(|year| year == solution_year)
+        && day.is_none_or(|day| day == solution_day)
+}
+
+// Answers drawn as pictures start on their own line.
+fn on_own_line(answer: &str) -> String {
+    if answer.contains('\n') {
+        format!("\n{}", answer)
+    } else {
+        format!(" {}", answer)
+    }
+}
@@ -0,0 +1,226 @@
+//! Measuring what each solution costs: how long it takes, the most memory it
+//! has allocated at once, and how many iterations of its main loop it ran.
+//!
+//! Peak allocation is only measured when `PeakAlloc` is the global
+//! allocator, as it is in the `aoc` binary. Iterations are only counted by
+//! solutions that report them with `report_iterations`; what an iteration is
+//! depends on the solution.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    error::Error,
+    fmt::Write,
+    sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::{Part, Solution};
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static ITERATIONS: AtomicU64 = AtomicU64::new(0);
+static COUNTED: AtomicBool = AtomicBool::new(false);
+
+/// The system allocator, keeping track of the most memory allocated at once.
+pub struct PeakAlloc;
+
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize)
+            -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+            grow(new_size);
+        }
+        new_ptr
+    }
+}
+
+fn grow(size: usize) {
+    let allocated = ALLOCATED.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(allocated, Ordering::Relaxed);
+}
+
+/// Record how many iterations the running solution took. Only the last
+/// number reported for each part is kept.
+pub fn report_iterations(iterations: u64) {
+    ITERATIONS.store(iterations, Ordering::Relaxed);
+    COUNTED.store(true, Ordering::Relaxed);
+}
+
+/// What solving one part cost.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Profile {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    /// The input that was solved.
+    pub input: String,
+    pub elapsed: Duration,
+    /// The most memory allocated at once while solving, in bytes, on top of
+    /// what was allocated beforehand.
+    pub peak: usize,
+    pub iterations: Option<u64>,
+}
+
+/// Solve one part on `text`, measuring what it costs. Only one part may be
+/// profiled at a time.
+pub fn profile(solution: &dyn Solution, part: Part, input: String,
+        text: &str) -> Result<Profile, Box<dyn Error>> {
+    COUNTED.store(false, Ordering::Relaxed);
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    PEAK.store(before, Ordering::Relaxed);
+    let start = Instant::now();
+    let answer = solution.solve(part, text)?;
+    let elapsed = start.elapsed();
+    drop(answer);
+    let peak = PEAK.load(Ordering::Relaxed).saturating_sub(before);
+    let iterations = if COUNTED.load(Ordering::Relaxed) {
+        Some(ITERATIONS.load(Ordering::Relaxed))
+    } else {
+        None
+    };
+    Ok(Profile {
+        year: solution.year(),
+        day: solution.day(),
+        part,
+        input,
+        elapsed,
+        peak,
+        iterations,
+    })
+}
+
+/// The profiles as a table, one row for each.
+pub fn table(profiles: &[Profile]) -> String {
+    let mut table = String::new();
+    writeln!(table, "year  day  part        time        peak  iterations")
+        .unwrap();
+    for profile in profiles {
+        let iterations = match profile.iterations {
+            Some(iterations) => iterations.to_string(),
+            None => "-".to_string(),
+        };
+        writeln!(table, "{}  {:>3}  {:>4}  {:>10}  {:>10}  {:>10}",
+            profile.year, profile.day, profile.part.to_string(),
+            format!("{:.1?}", profile.elapsed), bytes(profile.peak),
+            iterations).unwrap();
+    }
+    table
+}
+
+// A number of bytes, in the largest unit that keeps it at least 1.
+fn bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while 1024.0 <= size && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if 0 == unit {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// The profiles as a JSON array, with the time in nanoseconds and the peak
+/// allocation in bytes.
+pub fn json(profiles: &[Profile]) -> String {
+    let mut json = String::from("[");
+    for (index, profile) in profiles.iter().enumerate() {
+        if 0 < index {
+            json.push(',');
+        }
+        let iterations = match profile.iterations {
+            Some(iterations) => iterations.to_string(),
+            None => "null".to_string(),
+        };
+        write!(json, "\n  {{\"year\": {}, \"day\": {}, \"part\": {}, \
+            \"input\": {}, \"nanos\": {}, \"peak_bytes\": {}, \
+            \"iterations\": {}}}", profile.year, profile.day, profile.part,
+            json_string(&profile.input), profile.elapsed.as_nanos(),
+            profile.peak, iterations).unwrap();
+    }
+    json.push_str("\n]\n");
+    json
+}
+
+fn json_string(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c if c.is_control() => {
+                write!(quoted, "\\u{:04x}", u32::from(c)).unwrap();
+            },
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example(iterations: Option<u64>) -> Profile {
+        Profile {
+            year: 2015,
+            day: 4,
+            part: Part::Two,
+            input: "\"bgvyzdsv\"".to_string(),
+            elapsed: Duration::from_micros(1500),
+            peak: 3 << 19,
+            iterations,
+        }
+    }
+
+    #[test]
+    fn formats_tables() {
+        assert_eq!("\
+year  day  part        time        peak  iterations
+2015    4     2       1.5ms     1.5 MiB     1038736
+2015    4     2       1.5ms     1.5 MiB           -
+", table(&[example(Some(1038736)), example(None)]));
+        assert_eq!("512 B", bytes(512));
+        assert_eq!("2.0 KiB", bytes(2048));
+    }
+
+    #[test]
+    fn formats_json() {
+        assert_eq!("[
+  {\"year\": 2015, \"day\": 4, \"part\": 2, \"input\": \"\\\"bgvyzdsv\\\"\", \
+\"nanos\": 1500000, \"peak_bytes\": 1572864, \"iterations\": 1038736},
+  {\"year\": 2015, \"day\": 4, \"part\": 2, \"input\": \"\\\"bgvyzdsv\\\"\", \
+\"nanos\": 1500000, \"peak_bytes\": 1572864, \"iterations\": null}
+]
+", json(&[example(Some(1038736)), example(None)]));
+        assert_eq!("[\n]\n", json(&[]));
+    }
+}
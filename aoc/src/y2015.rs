@@ -0,0 +1,117 @@
+use std::error::Error;
+
+use crate::{Answer, Input, Part, Solution, profile::report_iterations};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    fn year(&self) -> u16 { 2015 }
+    fn day(&self) -> u8 { 1 }
+
+    fn input(&self, _: Part) -> Input<'static> {
+        Input::File("2015/day1/input.txt")
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        Ok(Box::new(aoc_2015_day1::final_floor(input)))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        let position = aoc_2015_day1::first_basement_position(input)
+            .ok_or("Santa never went below ground level.")?;
+        Ok(Box::new(position))
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    fn year(&self) -> u16 { 2015 }
+    fn day(&self) -> u8 { 2 }
+
+    fn input(&self, _: Part) -> Input<'static> {
+        Input::File("2015/day2/input.txt")
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        Ok(Box::new(aoc_2015_day2::wrapping_paper(input)?))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        Ok(Box::new(aoc_2015_day2::ribbon(input)?))
+    }
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    fn year(&self) -> u16 { 2015 }
+    fn day(&self) -> u8 { 3 }
+
+    fn input(&self, _: Part) -> Input<'static> {
+        Input::File("2015/day3/input.txt")
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        Ok(Box::new(aoc_2015_day3::houses_visited(input, 1)))
+    }
+
+    // Santa and Robo-Santa take turns.
+    fn part2(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        Ok(Box::new(aoc_2015_day3::houses_visited(input, 2)))
+    }
+}
+
+pub struct Day4;
+
+impl Day4 {
+    // The secret key, which has to be more than whitespace.
+    fn key(input: &str) -> Result<&str, Box<dyn Error>> {
+        match input.trim() {
+            "" => Err("The secret key is empty.".into()),
+            key => Ok(key),
+        }
+    }
+}
+
+impl Solution for Day4 {
+    fn year(&self) -> u16 { 2015 }
+    fn day(&self) -> u8 { 4 }
+
+    fn input(&self, _: Part) -> Input<'static> {
+        Input::Text("bgvyzdsv")
+    }
+
+    // Every number up to the answer is hashed.
+    fn part1(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        let number = aoc_2015_day4::mine(Self::key(input)?, 5);
+        report_iterations(number);
+        Ok(Box::new(number))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        let number = aoc_2015_day4::mine(Self::key(input)?, 6);
+        report_iterations(number);
+        Ok(Box::new(number))
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    fn year(&self) -> u16 { 2015 }
+    fn day(&self) -> u8 { 6 }
+
+    fn input(&self, _: Part) -> Input<'static> {
+        Input::File("2015/day6/input.txt")
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        Ok(Box::new(aoc_2015_day6::lights_lit(input)?))
+    }
+
+    // The instructions were really for brightness.
+    fn part2(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        Ok(Box::new(aoc_2015_day6::total_brightness(input)?))
+    }
+}
@@ -0,0 +1,249 @@
+use std::error::Error;
+
+use aoc_2019_day7::Wiring;
+use intcode::{Intcode, parse_program};
+
+use crate::{Answer, Input, Part, Solution, profile::report_iterations};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    fn year(&self) -> u16 { 2019 }
+    fn day(&self) -> u8 { 1 }
+
+    fn input(&self, _: Part) -> Input<'static> {
+        Input::File("2019/day1/input.txt")
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        let fuel = aoc_2019_day1::fuel_for_mass;
+        Ok(Box::new(aoc_2019_day1::total_fuel(input, fuel)?))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        let fuel = aoc_2019_day1::fuel_for_module;
+        Ok(Box::new(aoc_2019_day1::total_fuel(input, fuel)?))
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    fn year(&self) -> u16 { 2019 }
+    fn day(&self) -> u8 { 2 }
+
+    fn input(&self, _: Part) -> Input<'static> {
+        Input::File("2019/day2/input.txt")
+    }
+
+    // Restore the "1202 program alarm" state.
+    fn part1(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        let program = parse_program(input)?;
+        Ok(Box::new(aoc_2019_day2::run_nounverb(&program, 12, 2)?))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        let program = parse_program(input)?;
+        let (noun, verb) = aoc_2019_day2::find_nounverb(&program)?
+            .ok_or("Couldn't find the correct noun and verb.")?;
+        Ok(Box::new(100*noun + verb))
+    }
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    fn year(&self) -> u16 { 2019 }
+    fn day(&self) -> u8 { 3 }
+
+    fn input(&self, _: Part) -> Input<'static> {
+        Input::File("2019/day3/input.txt")
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        let distance = aoc_2019_day3::closest_crossing(input)?
+            .ok_or("The wires never cross.")?;
+        Ok(Box::new(distance))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        let steps = aoc_2019_day3::fewest_steps(input)?
+            .ok_or("The wires never cross.")?;
+        Ok(Box::new(steps))
+    }
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    fn year(&self) -> u16 { 2019 }
+    fn day(&self) -> u8 { 4 }
+
+    fn input(&self, _: Part) -> Input<'static> {
+        Input::Text("372304-847060")
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        Ok(Box::new(aoc_2019_day4::count_loose(input)?))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        Ok(Box::new(aoc_2019_day4::count_strict(input)?))
+    }
+}
+
+pub struct Day5;
+
+impl Day5 {
+    // Run the diagnostic program for the system with the given ID. Every
+    // test outputs 0 if it passes, and the last output is the diagnostic
+    // code. Each instruction executed counts as an iteration.
+    fn diagnose(input: &str, system: i64) -> Result<Answer, Box<dyn Error>> {
+        let mut vm = Intcode::new(parse_program(input)?);
+        vm.push_input(system);
+        let outputs = vm.run_to_halt()?;
+        report_iterations(vm.steps());
+        let (&code, tests) = outputs
+            .split_last()
+            .ok_or("The diagnostic program produced no output.")?;
+        if let Some(failed) = tests.iter().position(|&value| 0 != value) {
+            return Err(format!("Diagnostic test {} failed.", failed).into());
+        }
+        Ok(Box::new(code))
+    }
+}
+
+impl Solution for Day5 {
+    fn year(&self) -> u16 { 2019 }
+    fn day(&self) -> u8 { 5 }
+
+    fn input(&self, _: Part) -> Input<'static> {
+        Input::File("2019/day5/input/day5-input.txt")
+    }
+
+    // The air conditioner unit is system 1.
+    fn part1(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        Self::diagnose(input, 1)
+    }
+
+    // The thermal radiator controller is system 5.
+    fn part2(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        Self::diagnose(input, 5)
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    fn year(&self) -> u16 { 2019 }
+    fn day(&self) -> u8 { 6 }
+
+    fn input(&self, _: Part) -> Input<'static> {
+        Input::File("2019/day6/input.txt")
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        Ok(Box::new(aoc_2019_day6::total_orbits(input)?))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        Ok(Box::new(aoc_2019_day6::transfers_to_santa(input)?))
+    }
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    fn year(&self) -> u16 { 2019 }
+    fn day(&self) -> u8 { 7 }
+
+    fn input(&self, part: Part) -> Input<'static> {
+        match part {
+            Part::One => Input::File("2019/day7/input/day7-part1-input.txt"),
+            Part::Two => Input::File("2019/day7/input/day7-part2-input.txt"),
+        }
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        let program = parse_program(input)?;
+        let (_, signal) =
+            aoc_2019_day7::solve(&program, &[0, 1, 2, 3, 4], Wiring::Series)?;
+        Ok(Box::new(signal))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        let program = parse_program(input)?;
+        let (_, signal) = aoc_2019_day7::solve(&program, &[5, 6, 7, 8, 9],
+            Wiring::Feedback)?;
+        Ok(Box::new(signal))
+    }
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    fn year(&self) -> u16 { 2019 }
+    fn day(&self) -> u8 { 8 }
+
+    fn input(&self, _: Part) -> Input<'static> {
+        Input::File("2019/day8/input.txt")
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        Ok(Box::new(aoc_2019_day8::checksum(input)?))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        Ok(Box::new(aoc_2019_day8::message(input)?))
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    fn year(&self) -> u16 { 2019 }
+    fn day(&self) -> u8 { 10 }
+
+    fn input(&self, part: Part) -> Input<'static> {
+        match part {
+            Part::One => Input::File("2019/day10/input/day10_part1_input.txt"),
+            Part::Two => Input::File("2019/day10/input/day10_part2_input.txt"),
+        }
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        let (_, count) = aoc_2019_day10::best_location(input)?;
+        Ok(Box::new(count))
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        let answer = aoc_2019_day10::vaporize(input)?
+            .ok_or("There are fewer than 200 asteroids to vaporize.")?;
+        Ok(Box::new(answer))
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    fn year(&self) -> u16 { 2019 }
+    fn day(&self) -> u8 { 12 }
+
+    fn input(&self, part: Part) -> Input<'static> {
+        match part {
+            Part::One => Input::File("2019/day12/input/day12-part1-input.txt"),
+            Part::Two => Input::File("2019/day12/input/day12-part2-input.txt"),
+        }
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        Ok(Box::new(aoc_2019_day12::total_energy(input, 1000)?))
+    }
+
+    // Each time step simulated counts as an iteration.
+    fn part2(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        let (period, steps) = aoc_2019_day12::find_repeat_period(input)?;
+        report_iterations(steps as u64);
+        Ok(Box::new(period))
+    }
+}
@@ -0,0 +1,48 @@
+//! Profiling with `PeakAlloc` installed, as it is in the `aoc` binary.
+//!
+//! This is the only test in its own binary, because the allocator counts
+//! every thread's allocations: other tests running alongside would show up
+//! in the peak.
+
+use std::error::Error;
+
+use aoc::{
+    Answer, Input, Part, Solution,
+    profile::{PeakAlloc, profile, report_iterations},
+};
+
+#[global_allocator]
+static ALLOCATOR: PeakAlloc = PeakAlloc;
+
+// Allocates a megabyte and counts to three.
+struct Greedy;
+
+impl Solution for Greedy {
+    fn year(&self) -> u16 { 2019 }
+    fn day(&self) -> u8 { 1 }
+
+    fn input(&self, _: Part) -> Input<'static> {
+        Input::Text("")
+    }
+
+    fn part1(&self, _: &str) -> Result<Answer, Box<dyn Error>> {
+        let buffer = vec![1u8; 1 << 20];
+        report_iterations(3);
+        Ok(Box::new(buffer.len()))
+    }
+
+    fn part2(&self, _: &str) -> Result<Answer, Box<dyn Error>> {
+        Ok(Box::new(0))
+    }
+}
+
+#[test]
+fn measures_solutions() {
+    let part1 = profile(&Greedy, Part::One, String::new(), "").unwrap();
+    assert!(1 << 20 <= part1.peak);
+    assert_eq!(Some(3), part1.iterations);
+    // Other parts don't inherit the count.
+    let part2 = profile(&Greedy, Part::Two, String::new(), "").unwrap();
+    assert!(part2.peak < 1 << 20);
+    assert_eq!(None, part2.iterations);
+}
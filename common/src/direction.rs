@@ -0,0 +1,172 @@
+//! The directions a point can move in on a grid.
+
+use std::str::FromStr;
+
+use crate::parse::ParseError;
+
+/// One of the four directions along the axes of a grid.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Every direction, clockwise from `Up`.
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// Parse an arrow: `^`, `>`, `v`, or `<`.
+    pub fn from_arrow(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Self::Up),
+            '>' => Some(Self::Right),
+            'v' => Some(Self::Down),
+            '<' => Some(Self::Left),
+            _ => None,
+        }
+    }
+
+    /// Parse the first letter of the direction's name: `U`, `R`, `D`, or
+    /// `L`.
+    pub fn from_letter(c: char) -> Option<Self> {
+        match c {
+            'U' => Some(Self::Up),
+            'R' => Some(Self::Right),
+            'D' => Some(Self::Down),
+            'L' => Some(Self::Left),
+            _ => None,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1)%4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3)%4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2)%4]
+    }
+}
+
+/// Parses a letter, as `from_letter` does, or an arrow, as `from_arrow`
+/// does.
+impl FromStr for Direction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Self::from_letter(c).or_else(|| {
+                Self::from_arrow(c)
+            }),
+            _ => None,
+        }.ok_or_else(|| {
+            ParseError::new(1, "a direction").found(format!("{:?}", s))
+        })
+    }
+}
+
+/// One of the eight directions to a neighbouring cell of a grid, including
+/// diagonally.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// Every direction, clockwise from `Up`.
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// The directions along the axes that add up to this one.
+    pub fn components(self) -> &'static [Direction] {
+        match self {
+            Self::Up => &[Direction::Up],
+            Self::UpRight => &[Direction::Up, Direction::Right],
+            Self::Right => &[Direction::Right],
+            Self::DownRight => &[Direction::Down, Direction::Right],
+            Self::Down => &[Direction::Down],
+            Self::DownLeft => &[Direction::Down, Direction::Left],
+            Self::Left => &[Direction::Left],
+            Self::UpLeft => &[Direction::Up, Direction::Left],
+        }
+    }
+
+    /// The direction 45 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1)%8]
+    }
+
+    /// The direction 45 degrees counterclockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7)%8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4)%8]
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => Self::Up,
+            Direction::Right => Self::Right,
+            Direction::Down => Self::Down,
+            Direction::Left => Self::Left,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        assert_eq!(Direction::Right, Direction::Up.turn_right());
+        assert_eq!(Direction::Left, Direction::Up.turn_left());
+        assert_eq!(Direction::Down, Direction::Up.opposite());
+        assert_eq!(Direction::Up, Direction::Left.turn_right());
+        for &direction in Direction::ALL.iter() {
+            assert_eq!(direction, direction.turn_left().turn_right());
+            assert_eq!(Direction8::from(direction.opposite()),
+                Direction8::from(direction).opposite());
+        }
+        assert_eq!(Direction8::UpLeft, Direction8::Up.turn_left());
+        assert_eq!(Direction8::Up, Direction8::UpLeft.turn_right());
+        assert_eq!(Direction8::DownRight, Direction8::UpLeft.opposite());
+    }
+
+    #[test]
+    fn parses_directions() {
+        assert_eq!(Ok(Direction::Right), "R".parse());
+        assert_eq!(Ok(Direction::Down), "v".parse());
+        assert_eq!(Some(Direction::Left), Direction::from_arrow('<'));
+        assert_eq!(None, Direction::from_arrow('L'));
+        assert_eq!(Some(Direction::Up), Direction::from_letter('U'));
+        assert!("".parse::<Direction>().is_err());
+        assert!("RU".parse::<Direction>().is_err());
+        assert!("x".parse::<Direction>().is_err());
+    }
+}
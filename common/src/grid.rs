@@ -0,0 +1,303 @@
+//! Grids of cells, addressed by `Point2`.
+//!
+//! A `DenseGrid` stores every cell of a fixed rectangle, for grids that are
+//! mostly full, such as a puzzle's map. A `SparseGrid` stores only the cells
+//! that have been set, anywhere on an unbounded plane, for things like the
+//! houses Santa has visited.
+
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    iter::FromIterator,
+    ops::{Index, IndexMut},
+};
+
+use crate::{Integer, Point2, parse::ParseError};
+
+/// A `width` by `height` rectangle of cells, with `(0, 0)` in the corner.
+/// When parsed from text, row `y` is line `y`, so `y` increases downward.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct DenseGrid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> DenseGrid<T> {
+    /// A grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self { width, height, cells: vec![fill; width*height] }
+    }
+}
+
+impl<T> DenseGrid<T> {
+    /// Parse a grid with a character for each cell, and a line for each row.
+    /// `cell` turns a character into a cell, or `None` if it's not one. Every
+    /// row must be as wide as the first.
+    pub fn parse<F>(input: &str, mut cell: F) -> Result<Self, ParseError>
+            where F: FnMut(char) -> Option<T> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for (y, line) in input.lines().enumerate() {
+            let mut row_width = 0;
+            for (x, c) in line.chars().enumerate() {
+                let value = cell(c).ok_or_else(|| {
+                    ParseError::new(x + 1, "a grid cell")
+                        .found(format!("{:?}", c))
+                        .at_line(y + 1)
+                })?;
+                cells.push(value);
+                row_width += 1;
+            }
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    let error = ParseError::new(width.min(row_width) + 1,
+                        format!("a row {} cells wide", width));
+                    return Err(error
+                        .found(format!("{} cells", row_width))
+                        .at_line(y + 1));
+                },
+                Some(_) => (),
+            }
+            height += 1;
+        }
+        Ok(Self { width: width.unwrap_or(0), height, cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point2<usize>) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    pub fn get(&self, point: Point2<usize>) -> Option<&T> {
+        if self.contains(point) {
+            Some(&self.cells[point.y*self.width + point.x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, point: Point2<usize>) -> Option<&mut T> {
+        if self.contains(point) {
+            Some(&mut self.cells[point.y*self.width + point.x])
+        } else {
+            None
+        }
+    }
+
+    /// Every cell, row by row.
+    pub fn values(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2<usize>, &T)> {
+        let width = self.width;
+        self.cells.iter().enumerate().map(move |(index, cell)| {
+            (Point2::new(index%width, index/width), cell)
+        })
+    }
+
+    /// Every row, in order of `y`.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        // `chunks` can't take a size of 0.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The cells of row `y`. Panics if there's no such row.
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        assert!(y < self.height, "row {} of {}", y, self.height);
+        &mut self.cells[y*self.width..(y + 1)*self.width]
+    }
+}
+
+impl<T> Index<Point2<usize>> for DenseGrid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point2<usize>) -> &T {
+        let (width, height) = (self.width, self.height);
+        self.get(point).unwrap_or_else(|| {
+            panic!("{} is outside a {} by {} grid", point, width, height)
+        })
+    }
+}
+
+impl<T> IndexMut<Point2<usize>> for DenseGrid<T> {
+    fn index_mut(&mut self, point: Point2<usize>) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(point).unwrap_or_else(|| {
+            panic!("{} is outside a {} by {} grid", point, width, height)
+        })
+    }
+}
+
+/// The cells that have been set on an unbounded grid.
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T, C = i32> {
+    cells: HashMap<Point2<C>, T>,
+}
+
+impl<T, C: Integer> SparseGrid<T, C> {
+    pub fn new() -> Self {
+        Self { cells: HashMap::new() }
+    }
+
+    /// The number of cells that have been set.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, point: Point2<C>) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    pub fn get(&self, point: Point2<C>) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: Point2<C>) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    /// Set a cell, returning what it was before.
+    pub fn insert(&mut self, point: Point2<C>, cell: T) -> Option<T> {
+        self.cells.insert(point, cell)
+    }
+
+    /// Unset a cell, returning what it was.
+    pub fn remove(&mut self, point: Point2<C>) -> Option<T> {
+        self.cells.remove(&point)
+    }
+
+    pub fn entry(&mut self, point: Point2<C>) -> Entry<'_, Point2<C>, T> {
+        self.cells.entry(point)
+    }
+
+    /// Every cell that has been set, with its position, in no particular
+    /// order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2<C>, &T)> {
+        self.cells.iter().map(|(&point, cell)| (point, cell))
+    }
+
+    /// The corners of the smallest rectangle holding every cell that has
+    /// been set, lowest coordinates first, or `None` if none have been.
+    pub fn bounds(&self) -> Option<(Point2<C>, Point2<C>)> {
+        let mut points = self.cells.keys();
+        let first = *points.next()?;
+        Some(points.fold((first, first), |(min, max), &point| {
+            (Point2::new(min.x.min(point.x), min.y.min(point.y)),
+                Point2::new(max.x.max(point.x), max.y.max(point.y)))
+        }))
+    }
+}
+
+impl<T: PartialEq, C: Integer> PartialEq for SparseGrid<T, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cells == other.cells
+    }
+}
+
+impl<T: Eq, C: Integer> Eq for SparseGrid<T, C> {}
+
+impl<T, C: Integer> Default for SparseGrid<T, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, C: Integer> FromIterator<(Point2<C>, T)> for SparseGrid<T, C> {
+    fn from_iter<I>(iter: I) -> Self
+            where I: IntoIterator<Item = (Point2<C>, T)> {
+        Self { cells: iter.into_iter().collect() }
+    }
+}
+
+impl<T, C: Integer> Extend<(Point2<C>, T)> for SparseGrid<T, C> {
+    fn extend<I>(&mut self, iter: I)
+            where I: IntoIterator<Item = (Point2<C>, T)> {
+        self.cells.extend(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asteroid(c: char) -> Option<bool> {
+        match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn parses_dense_grids() {
+        let grid = DenseGrid::parse(".#.\n..#\n", asteroid).unwrap();
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert!(grid[Point2::new(1, 0)]);
+        assert!(grid[Point2::new(2, 1)]);
+        assert_eq!(Some(&false), grid.get(Point2::new(0, 1)));
+        assert_eq!(None, grid.get(Point2::new(3, 0)));
+        assert_eq!(None, grid.get(Point2::new(0, 2)));
+        let asteroids: Vec<Point2<usize>> = grid
+            .iter()
+            .filter(|&(_, &cell)| cell)
+            .map(|(point, _)| point)
+            .collect();
+        assert_eq!(vec![Point2::new(1, 0), Point2::new(2, 1)], asteroids);
+        let error = |input| DenseGrid::parse(input, asteroid).unwrap_err();
+        assert_eq!("2:3: expected a row 3 cells wide, found 2 cells",
+            error(".#.\n.#\n").to_string());
+        assert_eq!("1:3: expected a grid cell, found 'x'",
+            error(".#x\n").to_string());
+        let empty = DenseGrid::parse("", asteroid).unwrap();
+        assert_eq!((0, 0), (empty.width(), empty.height()));
+        assert_eq!(0, empty.rows().count());
+    }
+
+    #[test]
+    fn updates_dense_grids() {
+        let mut grid = DenseGrid::new(4, 3, 0);
+        grid[Point2::new(3, 2)] = 5;
+        *grid.get_mut(Point2::new(0, 1)).unwrap() += 1;
+        for cell in &mut grid.row_mut(0)[1..3] {
+            *cell = 2;
+        }
+        let rows: Vec<&[i32]> = grid.rows().collect();
+        assert_eq!(vec![&[0, 2, 2, 0][..], &[1, 0, 0, 0], &[0, 0, 0, 5]],
+            rows);
+        assert_eq!(10, grid.values().iter().sum::<i32>());
+    }
+
+    #[test]
+    fn sparse_grids() {
+        let mut grid: SparseGrid<u32> = SparseGrid::new();
+        assert_eq!(None, grid.bounds());
+        grid.insert(Point2::new(-2, 5), 1);
+        *grid.entry(Point2::new(3, -1)).or_insert(0) += 2;
+        *grid.entry(Point2::new(3, -1)).or_insert(0) += 2;
+        assert_eq!(2, grid.len());
+        assert_eq!(Some(&4), grid.get(Point2::new(3, -1)));
+        assert_eq!(Some((Point2::new(-2, -1), Point2::new(3, 5))),
+            grid.bounds());
+        assert_eq!(Some(1), grid.remove(Point2::new(-2, 5)));
+        assert!(!grid.contains(Point2::new(-2, 5)));
+        let grid: SparseGrid<(), i64> = vec![(Point2::new(1, 1), ())]
+            .into_iter()
+            .collect();
+        assert_eq!(1, grid.len());
+    }
+}
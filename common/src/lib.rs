@@ -0,0 +1,15 @@
+//! Building blocks shared by the solutions: points and directions on a grid,
+//! grids themselves, integer helpers, and the parsers that go with them,
+//! which all report malformed input with a `ParseError`.
+
+pub mod direction;
+pub mod grid;
+pub mod num;
+pub mod parse;
+pub mod point;
+
+pub use direction::{Direction, Direction8};
+pub use grid::{DenseGrid, SparseGrid};
+pub use num::{Integer, gcd, lcm};
+pub use parse::ParseError;
+pub use point::{Point2, Point3, Vector2, Vector3};
@@ -0,0 +1,111 @@
+//! Integer helpers.
+
+use std::{
+    fmt::{Debug, Display},
+    hash::Hash,
+    ops::{Add, AddAssign, Div, Mul, Rem, Sub, SubAssign},
+    str::FromStr,
+};
+
+/// The operations the shared types need from an integer, implemented for
+/// every primitive integer type.
+pub trait Integer: Copy + Debug + Default + Display + FromStr + Hash + Ord
+    + Add<Output = Self> + AddAssign + Sub<Output = Self> + SubAssign
+    + Mul<Output = Self> + Div<Output = Self> + Rem<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// The distance from zero. Unsigned integers are their own.
+    fn magnitude(self) -> Self;
+
+    /// The distance between two integers, which never overflows for
+    /// unsigned integers.
+    fn distance(self, other: Self) -> Self {
+        if self < other { other - self } else { self - other }
+    }
+}
+
+macro_rules! impl_signed {
+    ($($t:ty)*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn magnitude(self) -> Self {
+                self.abs()
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty)*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn magnitude(self) -> Self {
+                self
+            }
+        }
+    )*};
+}
+
+impl_signed!(i8 i16 i32 i64 i128 isize);
+impl_unsigned!(u8 u16 u32 u64 u128 usize);
+
+/// The greatest common divisor of `a` and `b`, which is never negative.
+/// `gcd(0, 0)` is 0.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let mut a = a.magnitude();
+    let mut b = b.magnitude();
+    while T::ZERO != b {
+        let temp = b;
+        b = a%b;
+        a = temp;
+    }
+    a
+}
+
+/// The least common multiple of `a` and `b`, which is never negative. It's
+/// 0 if either is.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if T::ZERO == a || T::ZERO == b {
+        return T::ZERO;
+    }
+    a.magnitude()/gcd(a, b)*b.magnitude()
+}
+
+/// The least common multiple of every number, or 1 if there are none.
+pub fn lcm_all<T, I>(numbers: I) -> T
+        where T: Integer, I: IntoIterator<Item = T> {
+    numbers.into_iter().fold(T::ONE, lcm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(6, gcd(12, 18));
+        assert_eq!(6, gcd(-12, 18));
+        assert_eq!(6, gcd(12, -18));
+        assert_eq!(5, gcd(0, -5));
+        assert_eq!(0, gcd(0, 0));
+        assert_eq!(1, gcd(17u64, 5));
+        assert_eq!(36, lcm(12, -18));
+        assert_eq!(0, lcm(0, 3));
+        assert_eq!(2772, lcm_all(vec![18usize, 28, 44]));
+        assert_eq!(1, lcm_all(Vec::<i32>::new()));
+    }
+
+    #[test]
+    fn distances() {
+        assert_eq!(7, 3i32.distance(-4));
+        assert_eq!(7, 3u8.distance(10));
+        assert_eq!(7, 10u8.distance(3));
+        assert_eq!(4, (-4i64).magnitude());
+        assert_eq!(4, 4usize.magnitude());
+    }
+}
@@ -0,0 +1,302 @@
+//! Parsers for the shapes puzzle inputs usually come in, and the error they
+//! all return.
+//!
+//! A `FromStr` impl knows the column where its string went wrong, but not
+//! which line of which file the string came from. So a `ParseError` starts
+//! out with just a column, and gains a line and a source as it's passed back
+//! up through `lines` and whoever read the file.
+
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, self},
+    str::FromStr,
+};
+
+/// Input that couldn't be parsed, and where.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    /// Where the input came from, such as a file name.
+    pub source: Option<String>,
+    /// The line the error is on, counting from 1.
+    pub line: Option<usize>,
+    /// The character the error is at, counting from 1.
+    pub column: usize,
+    /// What should have been there, such as `'x='` or `an integer`.
+    pub expected: String,
+    /// What was there instead, if it's worth saying.
+    pub found: Option<String>,
+}
+
+impl ParseError {
+    pub fn new<S: Into<String>>(column: usize, expected: S) -> Self {
+        Self {
+            source: None,
+            line: None,
+            column,
+            expected: expected.into(),
+            found: None,
+        }
+    }
+
+    pub fn found<S: Into<String>>(self, found: S) -> Self {
+        Self { found: Some(found.into()), ..self }
+    }
+
+    /// Move the error `columns` characters to the right, for when the
+    /// string that was parsed started partway through its line.
+    pub fn offset(self, columns: usize) -> Self {
+        Self { column: self.column + columns, ..self }
+    }
+
+    pub fn at_line(self, line: usize) -> Self {
+        Self { line: Some(line), ..self }
+    }
+
+    pub fn with_source<S: Into<String>>(self, source: S) -> Self {
+        Self { source: Some(source.into()), ..self }
+    }
+}
+
+/// Written as `input.txt:3:7: expected 'x=', found 'q'`, leaving out what
+/// isn't known.
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(source) = &self.source {
+            write!(f, "{}:", source)?;
+        }
+        match self.line {
+            Some(line) => write!(f, "{}:{}: ", line, self.column)?,
+            None if self.source.is_some() => {
+                write!(f, " column {}: ", self.column)?
+            },
+            None => write!(f, "column {}: ", self.column)?,
+        }
+        write!(f, "expected {}", self.expected)?;
+        if let Some(found) = &self.found {
+            write!(f, ", found {}", found)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+/// Steps through a string a piece at a time, keeping track of the column.
+#[derive(Clone, Debug)]
+pub struct Cursor<'a> {
+    rest: &'a str,
+    column: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(s: &'a str) -> Self {
+        Self { rest: s, column: 1 }
+    }
+
+    /// The column of the next character, counting from 1.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// What's left to parse.
+    pub fn rest(&self) -> &'a str {
+        self.rest
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.rest.chars().next()
+    }
+
+    pub fn next_char(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.advance(c.len_utf8());
+        Some(c)
+    }
+
+    /// An error at the next character, saying what was found there.
+    pub fn error<S: Into<String>>(&self, expected: S) -> ParseError {
+        let found = match self.peek() {
+            Some(c) => format!("{:?}", c),
+            None => "the end of the line".to_string(),
+        };
+        ParseError::new(self.column, expected).found(found)
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    /// Take the characters up to the first that doesn't match `pred`.
+    pub fn take_while<P>(&mut self, mut pred: P) -> &'a str
+            where P: FnMut(char) -> bool {
+        let end = self
+            .rest
+            .find(|c| !pred(c))
+            .unwrap_or(self.rest.len());
+        let taken = &self.rest[..end];
+        self.advance(end);
+        taken
+    }
+
+    /// Take `token` if it's next, returning whether it was.
+    pub fn accept(&mut self, token: &str) -> bool {
+        if self.rest.starts_with(token) {
+            self.advance(token.len());
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Take `token`, which must be next.
+    pub fn expect(&mut self, token: &str) -> Result<(), ParseError> {
+        if self.accept(token) {
+            Ok(())
+        } else {
+            Err(self.error(format!("'{}'", token)))
+        }
+    }
+
+    /// Take an integer, with an optional sign.
+    pub fn integer<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let start = self.clone();
+        let sign = if self.accept("-") {
+            "-"
+        } else {
+            self.accept("+");
+            ""
+        };
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        if digits.is_empty() {
+            *self = start;
+            return Err(self.error("an integer"));
+        }
+        format!("{}{}", sign, digits).parse().map_err(|_| {
+            // Only a type that can't be negative fails to parse "-0".
+            let expected = if "-" == sign && "-0".parse::<T>().is_err() {
+                "a non-negative integer"
+            } else {
+                "a smaller integer"
+            };
+            ParseError::new(start.column, expected)
+                .found(format!("{}{}", sign, digits))
+        })
+    }
+
+    /// Check that there's nothing left but whitespace.
+    pub fn end(&mut self) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        if self.rest.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("the end of the line"))
+        }
+    }
+
+    fn advance(&mut self, bytes: usize) {
+        self.column += self.rest[..bytes].chars().count();
+        self.rest = &self.rest[bytes..];
+    }
+}
+
+/// Parse a list such as `R75,D30,U83`. Whitespace around each item is
+/// ignored, and errors are placed relative to the start of `s`.
+pub fn separated<T>(s: &str, separator: char) -> Result<Vec<T>, ParseError>
+        where T: FromStr<Err = ParseError> {
+    let mut column = 0;
+    let mut items = Vec::new();
+    for raw in s.trim_end().split(separator) {
+        let item = raw.trim_start();
+        let indent = raw[..raw.len() - item.len()].chars().count();
+        items.push(item
+            .trim_end()
+            .parse()
+            .map_err(|e: ParseError| e.offset(column + indent))?);
+        column += raw.chars().count() + 1;
+    }
+    Ok(items)
+}
+
+/// Parse each non-blank line, giving errors the line they're on.
+pub fn lines<T>(input: &str) -> Result<Vec<T>, ParseError>
+        where T: FromStr<Err = ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            line.parse().map_err(|e: ParseError| e.at_line(index + 1))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{Direction, Point3};
+
+    #[test]
+    fn formats_errors() {
+        let error = ParseError::new(7, "'x='");
+        assert_eq!("column 7: expected 'x='", error.to_string());
+        let error = error.at_line(3);
+        assert_eq!("3:7: expected 'x='", error.to_string());
+        let error = error.with_source("input.txt");
+        assert_eq!("input.txt:3:7: expected 'x='", error.to_string());
+        let error = error.found("'q'").offset(2);
+        assert_eq!("input.txt:3:9: expected 'x=', found 'q'",
+            error.to_string());
+        let error = ParseError::new(1, "an integer").with_source("<stdin>");
+        assert_eq!("<stdin>: column 1: expected an integer",
+            error.to_string());
+    }
+
+    #[test]
+    fn cursors() {
+        let mut cursor = Cursor::new("turn on 0,-12 é");
+        assert_eq!("turn", cursor.take_while(char::is_alphabetic));
+        cursor.skip_whitespace();
+        assert!(!cursor.accept("off"));
+        cursor.expect("on").unwrap();
+        cursor.skip_whitespace();
+        assert_eq!(Ok(0), cursor.integer::<i32>());
+        assert_eq!(Err(ParseError::new(10, "'.'").found("','")),
+            cursor.clone().expect("."));
+        cursor.expect(",").unwrap();
+        assert_eq!(Err(ParseError::new(11, "a non-negative integer")
+            .found("-12")), cursor.clone().integer::<u8>());
+        assert_eq!(Err(ParseError::new(1, "a smaller integer").found("300")),
+            Cursor::new("300").integer::<u8>());
+        assert_eq!(Ok(-12), cursor.integer::<i8>());
+        assert_eq!(Err(ParseError::new(15, "the end of the line")
+            .found("'é'")), cursor.end());
+        assert_eq!(Err(ParseError::new(15, "an integer").found("'é'")),
+            cursor.integer::<i32>());
+        assert_eq!(Some('é'), cursor.next_char());
+        assert_eq!(16, cursor.column());
+        assert_eq!(Ok(()), cursor.end());
+        assert_eq!(Err(ParseError::new(16, "'x'")
+            .found("the end of the line")), cursor.expect("x"));
+    }
+
+    #[test]
+    fn parses_separated_lists() {
+        let directions = vec![Direction::Right, Direction::Down];
+        assert_eq!(Ok(directions), separated("R, D\n", ','));
+        assert_eq!(Err(ParseError::new(4, "a direction").found("\"x\"")),
+            separated::<Direction>("R, x", ','));
+        assert_eq!(Err(ParseError::new(3, "a direction").found("\"\"")),
+            separated::<Direction>("R,,D", ','));
+    }
+
+    #[test]
+    fn parses_lines() {
+        let points = vec![Point3::new(-1, 0, 2), Point3::new(2, -10, -7)];
+        assert_eq!(Ok(points),
+            lines("<x=-1, y=0, z=2>\n\n<x=2, y=-10, z=-7>\n"));
+        let error = lines::<Point3<i32>>("<x=1, y=0, z=0>\n\n<x=1, q=2, z=3>")
+            .unwrap_err();
+        assert_eq!("3:7: expected 'y=', found 'q'", error.to_string());
+    }
+}
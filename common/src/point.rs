@@ -0,0 +1,297 @@
+//! Points and vectors in two and three dimensions.
+
+use std::{
+    fmt::{Display, Formatter, self},
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+use crate::{
+    Direction,
+    Direction8,
+    Integer,
+    parse::{Cursor, ParseError},
+};
+
+/// A point on a grid, or a vector between two points. `y` increases upward,
+/// so `Direction::Up` adds 1 to it.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A point in space, or a vector between two points.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// The difference between two `Point2`s.
+pub type Vector2<T> = Point2<T>;
+
+/// The difference between two `Point3`s.
+pub type Vector3<T> = Point3<T>;
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Integer> Point2<T> {
+    pub const ORIGIN: Self = Self::new(T::ZERO, T::ZERO);
+
+    /// The number of steps between two points, moving only along the axes.
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.distance(other.x) + self.y.distance(other.y)
+    }
+
+    /// The number of steps between two points, moving diagonally as well.
+    pub fn chebyshev(self, other: Self) -> T {
+        self.x.distance(other.x).max(self.y.distance(other.y))
+    }
+
+    /// The neighbouring point in `direction`. Panics when the neighbour
+    /// can't be represented, such as stepping left from `x == 0` with an
+    /// unsigned `T`.
+    pub fn step(self, direction: Direction) -> Self {
+        match direction {
+            Direction::Up => Self::new(self.x, self.y + T::ONE),
+            Direction::Down => Self::new(self.x, self.y - T::ONE),
+            Direction::Left => Self::new(self.x - T::ONE, self.y),
+            Direction::Right => Self::new(self.x + T::ONE, self.y),
+        }
+    }
+
+    /// The neighbouring point in `direction`, including diagonally. Panics
+    /// as `step` does.
+    pub fn step8(self, direction: Direction8) -> Self {
+        direction
+            .components()
+            .iter()
+            .fold(self, |point, &direction| point.step(direction))
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Integer> Point3<T> {
+    pub const ORIGIN: Self = Self::new(T::ZERO, T::ZERO, T::ZERO);
+
+    /// The number of steps between two points, moving only along the axes.
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.distance(other.x) + self.y.distance(other.y)
+            + self.z.distance(other.z)
+    }
+
+    /// The number of steps between two points, moving diagonally as well.
+    pub fn chebyshev(self, other: Self) -> T {
+        self.x
+            .distance(other.x)
+            .max(self.y.distance(other.y))
+            .max(self.z.distance(other.z))
+    }
+}
+
+// Component-wise arithmetic, and scaling by a number.
+macro_rules! impl_vector_ops {
+    ($point:ident { $($axis:ident),* }) => {
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self { $($axis: self.$axis + other.$axis),* }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $point<T> {
+            fn add_assign(&mut self, other: Self) {
+                $(self.$axis += other.$axis;)*
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self { $($axis: self.$axis - other.$axis),* }
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $point<T> {
+            fn sub_assign(&mut self, other: Self) {
+                $(self.$axis -= other.$axis;)*
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($axis: -self.$axis),* }
+            }
+        }
+
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, scale: T) -> Self {
+                Self { $($axis: self.$axis*scale),* }
+            }
+        }
+    };
+}
+
+impl_vector_ops!(Point2 { x, y });
+impl_vector_ops!(Point3 { x, y, z });
+
+/// Written as `x,y`.
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// Written as `<x=1, y=2, z=3>`.
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "<x={}, y={}, z={}>", self.x, self.y, self.z)
+    }
+}
+
+// Parse comma-separated coordinates, one for each of `axes`. The whole list
+// may be in brackets, and each coordinate may be labelled, as in `x=1`.
+fn coordinates<T: FromStr>(s: &str, axes: &[char])
+        -> Result<Vec<T>, ParseError> {
+    let mut cursor = Cursor::new(s);
+    cursor.skip_whitespace();
+    let close = match cursor.peek() {
+        Some('(') => Some(")"),
+        Some('<') => Some(">"),
+        Some('[') => Some("]"),
+        _ => None,
+    };
+    if close.is_some() {
+        cursor.next_char();
+    }
+    let mut coordinates = Vec::new();
+    for (index, axis) in axes.iter().enumerate() {
+        cursor.skip_whitespace();
+        if 0 < index {
+            cursor.expect(",")?;
+            cursor.skip_whitespace();
+        }
+        if cursor.peek().is_some_and(char::is_alphabetic) {
+            cursor.expect(&format!("{}=", axis))?;
+            cursor.skip_whitespace();
+        }
+        coordinates.push(cursor.integer()?);
+    }
+    if let Some(close) = close {
+        cursor.skip_whitespace();
+        cursor.expect(close)?;
+    }
+    cursor.end()?;
+    Ok(coordinates)
+}
+
+/// Parses `x,y`, optionally in brackets, with each coordinate optionally
+/// labelled, as in `(x=1, y=2)`.
+impl<T: FromStr> FromStr for Point2<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut coordinates = coordinates(s, &['x', 'y'])?.into_iter();
+        match (coordinates.next(), coordinates.next()) {
+            (Some(x), Some(y)) => Ok(Self { x, y }),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Parses `x,y,z`, optionally in brackets, with each coordinate optionally
+/// labelled, as in `<x=1, y=2, z=3>`.
+impl<T: FromStr> FromStr for Point3<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut coordinates = coordinates(s, &['x', 'y', 'z'])?.into_iter();
+        match (coordinates.next(), coordinates.next(), coordinates.next()) {
+            (Some(x), Some(y), Some(z)) => Ok(Self { x, y, z }),
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances() {
+        let a = Point2::new(1, -2);
+        let b = Point2::new(-3, 5);
+        assert_eq!(11, a.manhattan(b));
+        assert_eq!(7, a.chebyshev(b));
+        assert_eq!(3, Point2::new(1, 2).manhattan(Point2::ORIGIN));
+        let a = Point2::new(2usize, 9);
+        assert_eq!(10, a.manhattan(Point2::new(5, 2)));
+        let a = Point3::new(-1, 0, 2);
+        assert_eq!(3, a.manhattan(Point3::ORIGIN));
+        assert_eq!(2, a.chebyshev(Point3::ORIGIN));
+    }
+
+    #[test]
+    fn arithmetic() {
+        let mut a = Point2::new(1, 2);
+        a += Vector2::new(3, 4);
+        assert_eq!(Point2::new(4, 6), a);
+        assert_eq!(Point2::new(3, 4), a - Point2::new(1, 2));
+        assert_eq!(Point2::new(-8, -12), -a*2);
+        let mut b = Point3::new(1, 2, 3);
+        b -= Vector3::new(1, 1, 1);
+        assert_eq!(Point3::new(0, 1, 2), b);
+        assert_eq!(Point3::new(1, 3, 5), b + Point3::new(1, 2, 3));
+    }
+
+    #[test]
+    fn steps() {
+        let origin = Point2::<i32>::ORIGIN;
+        assert_eq!(Point2::new(0, 1), origin.step(Direction::Up));
+        assert_eq!(Point2::new(-1, 0), origin.step(Direction::Left));
+        assert_eq!(Point2::new(1, -1), origin.step8(Direction8::DownRight));
+        for &direction in Direction8::ALL.iter() {
+            assert_eq!(1, origin.step8(direction).chebyshev(origin));
+        }
+    }
+
+    #[test]
+    fn parses_points() {
+        assert_eq!(Ok(Point2::new(499, 0)), "499,0".parse());
+        assert_eq!(Ok(Point2::new(-1, 2)), "(x=-1, y=2)".parse());
+        assert_eq!(Ok(Point3::new(-1, 0, 2)), "<x=-1, y=0, z=2>".parse());
+        assert_eq!(Ok(Point3::new(1, 2, 3)), " 1, 2, 3 ".parse());
+        let error = |s: &str| s.parse::<Point3<i32>>().unwrap_err();
+        assert_eq!(ParseError::new(11, "','").found("'>'"),
+            error("<x=-1, y=0>"));
+        assert_eq!(ParseError::new(13, "'z='").found("'y'"),
+            error("<x=-1, y=0, y=2>"));
+        assert_eq!(ParseError::new(17, "'>'").found("the end of the line"),
+            error("<x=-1, y=0, z=2 "));
+        assert_eq!(ParseError::new(6, "the end of the line").found("','"),
+            error("1,2,3,4"));
+        assert_eq!(ParseError::new(1, "a non-negative integer").found("-1"),
+            "-1,0".parse::<Point2<usize>>().unwrap_err());
+        let point = Point3::new(4, -5, 6);
+        assert_eq!(Ok(point), point.to_string().parse());
+        let point = Point2::new(4, -5);
+        assert_eq!(Ok(point), point.to_string().parse());
+    }
+}